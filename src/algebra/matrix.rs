@@ -1,5 +1,5 @@
 use super::polynomials::Pol;
-use crate::{F2D, F3D};
+use crate::{Function, F2D, F3D};
 use std::fmt::Display;
 
 #[derive(Debug, PartialEq)]
@@ -36,6 +36,19 @@ impl<T> Matrix<T> {
         Self { mat, n_row, n_col }
     }
 
+    /// Entries of the matrix, row by row
+    pub fn as_slice(&self) -> &[T] {
+        &self.mat
+    }
+
+    pub(crate) fn n_row(&self) -> usize {
+        self.n_row
+    }
+
+    pub(crate) fn n_col(&self) -> usize {
+        self.n_col
+    }
+
     pub(crate) fn get(&self, row: usize, col: usize) -> &T {
         &self.mat[(row - 1) * self.n_row + col - 1]
     }
@@ -104,29 +117,77 @@ impl Matrix<f64> {
 
         mat_minus_identity.determinant()
     }
+
+    /// Calculate the eigenvalues of a symmetric matrix with Jacobi rotations, in increasing order
+    pub fn symmetric_eigenvalues(&self) -> Vec<f64> {
+        if self.n_col != self.n_row {
+            panic!("No eigenvalues of non-square matrix");
+        }
+
+        let n = self.n_row;
+        let mut mat = self.mat.clone();
+        for _ in 0..100 {
+            // Largest off-diagonal entry
+            let mut pivot = (0, 1, 0.);
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    if mat[i * n + j].abs() > pivot.2 {
+                        pivot = (i, j, mat[i * n + j].abs());
+                    }
+                }
+            }
+            let (p, q, off) = pivot;
+            let scale = mat.iter().fold(0f64, |acc, el| acc.max(el.abs()));
+            if off <= f64::EPSILON * scale {
+                break;
+            }
+
+            // Rotation zeroing mat[p][q]
+            let theta = (mat[q * n + q] - mat[p * n + p]) / (2. * mat[p * n + q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+            let c = 1. / (t * t + 1.).sqrt();
+            let s = t * c;
+            for k in 0..n {
+                let (kp, kq) = (mat[k * n + p], mat[k * n + q]);
+                mat[k * n + p] = c * kp - s * kq;
+                mat[k * n + q] = s * kp + c * kq;
+            }
+            for k in 0..n {
+                let (pk, qk) = (mat[p * n + k], mat[q * n + k]);
+                mat[p * n + k] = c * pk - s * qk;
+                mat[q * n + k] = s * pk + c * qk;
+            }
+        }
+
+        let mut eigenvalues: Vec<f64> = (0..n).map(|i| mat[i * n + i]).collect();
+        eigenvalues.sort_by(|a, b| a.total_cmp(b));
+        eigenvalues
+    }
 }
 
 macro_rules! impl_determinant{
-    (for $($t:ty),+) => {
+    ($vis:vis for $($t:ty),+) => {
         $(impl Matrix<$t> {
             /// Calculate determinant
-            pub fn determinant(&self) -> $t {
+            $vis fn determinant(&self) -> $t {
                 if self.n_row != self.n_col {
-                    panic!("Cant' calculate determinant of non-square matrix")
+                    panic!("Can't calculate the determinant of a non-square matrix")
                 }
 
-                if self.n_row == 2 {
-                    (self.mat[0].clone() * self.mat[3].clone())
-                        - (self.mat[1].clone() * self.mat[2].clone())
+                let get = |row, col| self.get(row, col).clone();
+                if self.n_row == 1 {
+                    get(1, 1)
+                } else if self.n_row == 2 {
+                    get(1, 1) * get(2, 2) - get(1, 2) * get(2, 1)
                 } else if self.n_row == 3 {
-                    self.get(1, 1) * self.get(2, 2) * self.get(3, 3)
-                        + self.get(1, 2) * self.get(2, 3) * self.get(3, 1)
-                        + self.get(1, 3) * self.get(2, 1) * self.get(3, 2)
-                        - self.get(3, 1) * self.get(2, 2) * self.get(1, 3)
-                        - self.get(3, 2) * self.get(2, 3) * self.get(1, 1)
-                        - self.get(3, 3) * self.get(2, 1) * self.get(1, 2)
+                    get(1, 1) * get(2, 2) * get(3, 3)
+                        + get(1, 2) * get(2, 3) * get(3, 1)
+                        + get(1, 3) * get(2, 1) * get(3, 2)
+                        - get(3, 1) * get(2, 2) * get(1, 3)
+                        - get(3, 2) * get(2, 3) * get(1, 1)
+                        - get(3, 3) * get(2, 1) * get(1, 2)
                 } else {
-                    let mut result: $t = Default::default();
+                    let mut result: Option<$t> = None;
                     for (idx, el) in self.get_row(1).iter().enumerate() {
                         let mut mat = self.get_rows(2, self.n_row).to_vec();
                         let mut index = 0;
@@ -143,17 +204,23 @@ macro_rules! impl_determinant{
                         });
 
                         let sub_mat = Matrix::new(mat, self.n_col - 1, self.n_col - 1);
-                        result += (-1_f64).powf((idx + 2) as f64) * el * sub_mat.determinant();
+                        let term = el.clone() * sub_mat.determinant();
+                        result = Some(match result {
+                            None => term,
+                            Some(acc) if idx % 2 == 0 => acc + term,
+                            Some(acc) => acc - term,
+                        });
                     }
 
-                    result
+                    result.expect("the matrix isn't empty")
                 }
             }
         })*
     }
 }
 
-impl_determinant!(for f64, Pol);
+impl_determinant!(pub for f64, Pol);
+impl_determinant!(pub(crate) for Function);
 
 impl Matrix<F2D> {
     /// Eval
@@ -224,5 +291,11 @@ fn test_mat() {
         4,
     );
     assert_eq!(mat.determinant(), 38.);
-    println!("{}", mat.determinant())
+    println!("{}", mat.determinant());
+
+    let mat = Matrix::new(vec![2., 1., 0., 1., 2., 0., 0., 0., -1.], 3, 3);
+    let eigenvalues = mat.symmetric_eigenvalues();
+    for (value, expected) in eigenvalues.iter().zip([-1., 1., 3.]) {
+        assert!((value - expected).abs() < 1e-12);
+    }
 }
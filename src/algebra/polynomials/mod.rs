@@ -63,4 +63,3 @@ impl Display for Pol {
         write!(f, "{}", result)
     }
 }
-
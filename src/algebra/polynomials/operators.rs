@@ -1,6 +1,6 @@
+use crate::algebra::polynomials::Pol;
 use std::cmp::PartialEq;
 use std::ops::{Add, AddAssign, Mul, Sub};
-use crate::algebra::polynomials::Pol;
 
 impl Add for Pol {
     type Output = Self;
@@ -1,6 +1,7 @@
+use std::fmt::Display;
 use std::ops::{Add, Div, Mul, Sub};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Rational {
     num: i32,
     den: u32,
@@ -14,7 +15,7 @@ impl Rational {
         }
 
         let mut num = num.abs();
-        let den: u32 = den.abs() as u32;
+        let den: u32 = den.unsigned_abs();
 
         let gcd = gcd(num as u32, den);
         if opposite_sign {
@@ -44,6 +45,79 @@ impl Rational {
     pub fn den(&self) -> i32 {
         self.den as i32
     }
+
+    /// Builds a reduced rational from i64 parts, None if it doesn't fit or den is zero
+    fn from_i64(num: i64, den: i64) -> Option<Self> {
+        if den == 0 {
+            return None;
+        }
+        let gcd = gcd_u64(num.unsigned_abs(), den.unsigned_abs());
+        let num = (num.unsigned_abs() / gcd) as i64 * if (num < 0) != (den < 0) { -1 } else { 1 };
+
+        Some(Rational {
+            num: i32::try_from(num).ok()?,
+            den: u32::try_from(den.unsigned_abs() / gcd).ok()?,
+        })
+    }
+    /// Addition returning None on overflow
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Rational::from_i64(
+            self.num as i64 * rhs.den as i64 + rhs.num as i64 * self.den as i64,
+            self.den as i64 * rhs.den as i64,
+        )
+    }
+    /// Subtraction returning None on overflow
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(Rational {
+            num: rhs.num.checked_neg()?,
+            den: rhs.den,
+        })
+    }
+    /// Multiplication returning None on overflow
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Rational::from_i64(
+            self.num as i64 * rhs.num as i64,
+            self.den as i64 * rhs.den as i64,
+        )
+    }
+    /// Division returning None on overflow or division by zero
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        Rational::from_i64(
+            self.num as i64 * rhs.den as i64,
+            self.den as i64 * rhs.num as i64,
+        )
+    }
+    /// Integer power returning None on overflow or division by zero
+    pub fn checked_pow(self, exp: i32) -> Option<Self> {
+        let num = (self.num as i64).checked_pow(exp.unsigned_abs())?;
+        let den = (self.den as i64).checked_pow(exp.unsigned_abs())?;
+
+        if exp < 0 {
+            Rational::from_i64(den, num)
+        } else {
+            Rational::from_i64(num, den)
+        }
+    }
+    /// N-th root, None if the result is not rational
+    pub fn checked_root(self, n: u32) -> Option<Self> {
+        if self.num < 0 && n.is_multiple_of(2) {
+            return None;
+        }
+        let num = int_root(self.num.unsigned_abs(), n)? as i64;
+        let den = int_root(self.den, n)? as i64;
+
+        Rational::from_i64(if self.num < 0 { -num } else { num }, den)
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
 }
 
 impl PartialEq<i32> for Rational {
@@ -51,11 +125,6 @@ impl PartialEq<i32> for Rational {
         self.num == *other && self.den == 1
     }
 }
-impl PartialEq for Rational {
-    fn eq(&self, other: &Self) -> bool {
-        self.num == other.num && self.den == other.den
-    }
-}
 
 impl Add for Rational {
     type Output = Self;
@@ -130,6 +199,23 @@ pub fn gcd(mut n: u32, mut m: u32) -> u32 {
 fn lcm(first: u32, second: u32) -> u32 {
     first * second / gcd(first, second)
 }
+fn gcd_u64(mut n: u64, mut m: u64) -> u64 {
+    while m != 0 {
+        (n, m) = (m, n % m);
+    }
+    n
+}
+/// Exact integer n-th root
+fn int_root(val: u32, n: u32) -> Option<u32> {
+    let root = (val as f64).powf(1. / n as f64).round() as u64;
+    (root.saturating_sub(1)..=root + 1).find_map(|root| {
+        if root.checked_pow(n)? == val as u64 {
+            Some(root as u32)
+        } else {
+            None
+        }
+    })
+}
 
 #[test]
 fn test_rat_operators() {
@@ -149,3 +235,23 @@ fn test_rat_operators() {
     let b = Rational::new(2, 3);
     assert_eq!(a / b, Rational::new(-3, 4));
 }
+
+#[test]
+fn test_rat_checked() {
+    let a = Rational::new(1, 3);
+    let b = Rational::new(1, 4);
+    assert_eq!(a.clone().checked_add(b.clone()), Some(Rational::new(7, 12)));
+    assert_eq!(a.clone().checked_sub(b.clone()), Some(Rational::new(1, 12)));
+    assert_eq!(a.clone().checked_div(Rational::zero()), None);
+    assert_eq!(b.clone().checked_pow(-2), Some(Rational::new_from_int(16)));
+    assert_eq!(
+        Rational::new_from_int(1 << 20).checked_mul(Rational::new_from_int(1 << 20)),
+        None
+    );
+    assert_eq!(
+        Rational::new(-27, 8).checked_root(3),
+        Some(Rational::new(-3, 2))
+    );
+    assert_eq!(Rational::new_from_int(2).checked_root(2), None);
+    assert_eq!(Rational::new(-7, 12).to_string(), "-7/12");
+}
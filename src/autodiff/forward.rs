@@ -0,0 +1,299 @@
+use crate::scalar::Scalar;
+use crate::{Vec2, Vec3, F1D, F2D, F3D};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Dual number carrying a value and its gradient with respect to N variables
+pub struct Dual<const N: usize> {
+    /// Value of the function
+    pub value: f64,
+    /// Partial derivatives of the function
+    pub grad: [f64; N],
+}
+
+impl<const N: usize> Dual<N> {
+    /// Creates a dual number that doesn't depend on any variable
+    pub fn constant(value: f64) -> Self {
+        Dual {
+            value,
+            grad: [0.; N],
+        }
+    }
+
+    /// Creates the dual number of the i-th variable (starting from 0)
+    pub fn variable(value: f64, i: usize) -> Self {
+        let mut grad = [0.; N];
+        grad[i] = 1.;
+        Dual { value, grad }
+    }
+
+    /// Applies a function with the given value and derivative (chain rule)
+    fn chain(self, value: f64, derivative: f64) -> Self {
+        Dual {
+            value,
+            grad: self.grad.map(|g| g * derivative),
+        }
+    }
+
+    fn is_constant(&self) -> bool {
+        self.grad.iter().all(|g| *g == 0.)
+    }
+}
+
+impl<const N: usize> Scalar for Dual<N> {
+    fn from_f64(val: f64) -> Self {
+        Dual::constant(val)
+    }
+
+    fn pow(self, rhs: Self) -> Self {
+        let value = self.value.powf(rhs.value);
+
+        if rhs.is_constant() {
+            return self.chain(value, rhs.value * self.value.powf(rhs.value - 1.));
+        }
+
+        // d(f^g) = f^g(g'ln(f)+gf'/f)
+        let ln = self.value.ln();
+        let mut grad = [0.; N];
+        for (i, g) in grad.iter_mut().enumerate() {
+            *g = value * (rhs.grad[i] * ln + rhs.value * self.grad[i] / self.value);
+        }
+        Dual { value, grad }
+    }
+
+    fn ln(self) -> Self {
+        self.chain(self.value.ln(), 1. / self.value)
+    }
+    fn sin(self) -> Self {
+        self.chain(self.value.sin(), self.value.cos())
+    }
+    fn cos(self) -> Self {
+        self.chain(self.value.cos(), -self.value.sin())
+    }
+    fn tan(self) -> Self {
+        self.chain(self.value.tan(), 1. / self.value.cos().powi(2))
+    }
+    fn asin(self) -> Self {
+        let x = self.value;
+        self.chain(x.asin(), 1. / (1. - x * x).sqrt())
+    }
+    fn acos(self) -> Self {
+        let x = self.value;
+        self.chain(x.acos(), -1. / (1. - x * x).sqrt())
+    }
+    fn atan(self) -> Self {
+        let x = self.value;
+        self.chain(x.atan(), 1. / (1. + x * x))
+    }
+    fn sinh(self) -> Self {
+        self.chain(self.value.sinh(), self.value.cosh())
+    }
+    fn cosh(self) -> Self {
+        self.chain(self.value.cosh(), self.value.sinh())
+    }
+    fn tanh(self) -> Self {
+        self.chain(self.value.tanh(), 1. / self.value.cosh().powi(2))
+    }
+    fn asinh(self) -> Self {
+        let x = self.value;
+        self.chain(x.asinh(), 1. / (x * x + 1.).sqrt())
+    }
+    fn acosh(self) -> Self {
+        let x = self.value;
+        self.chain(x.acosh(), 1. / (x * x - 1.).sqrt())
+    }
+    fn atanh(self) -> Self {
+        let x = self.value;
+        self.chain(x.atanh(), 1. / (1. - x * x))
+    }
+    fn abs(self) -> Self {
+        self.chain(self.value.abs(), self.value.signum())
+    }
+}
+
+impl<const N: usize> Add for Dual<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut grad = self.grad;
+        for (i, g) in grad.iter_mut().enumerate() {
+            *g += rhs.grad[i];
+        }
+        Dual {
+            value: self.value + rhs.value,
+            grad,
+        }
+    }
+}
+impl<const N: usize> Sub for Dual<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+impl<const N: usize> Mul for Dual<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut grad = [0.; N];
+        for (i, g) in grad.iter_mut().enumerate() {
+            *g = self.grad[i] * rhs.value + self.value * rhs.grad[i];
+        }
+        Dual {
+            value: self.value * rhs.value,
+            grad,
+        }
+    }
+}
+impl<const N: usize> Div for Dual<N> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let mut grad = [0.; N];
+        for (i, g) in grad.iter_mut().enumerate() {
+            *g = (self.grad[i] * rhs.value - self.value * rhs.grad[i]) / (rhs.value * rhs.value);
+        }
+        Dual {
+            value: self.value / rhs.value,
+            grad,
+        }
+    }
+}
+impl<const N: usize> Neg for Dual<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Dual {
+            value: -self.value,
+            grad: self.grad.map(|g| -g),
+        }
+    }
+}
+
+impl F1D {
+    /// Evaluate F1D and its derivative at a given x in a single pass
+    /// ```
+    /// use ruut_functions::{F1D, approx};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("xsin(x)").unwrap();
+    /// let (value, derivative) = func.eval_with_derivative(2.);
+    ///
+    /// assert_eq!(approx(value, 5), 1.81859);
+    /// assert_eq!(approx(derivative, 5), 0.07700);
+    /// ```
+    pub fn eval_with_derivative(&self, x: f64) -> (f64, f64) {
+        let result = self.0.evaluate(&[
+            Dual::<1>::variable(x, 0),
+            Dual::constant(0.),
+            Dual::constant(0.),
+        ]);
+        (result.value, result.grad[0])
+    }
+}
+impl F2D {
+    /// Evaluate F2D and its gradient at a given (x,y) in a single pass
+    /// ```
+    /// use ruut_functions::{F2D, Vec2};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("x^2y+y").unwrap();
+    ///
+    /// assert_eq!(func.eval_with_gradient(3., 2.), (20., Vec2 { x: 12., y: 10. }));
+    /// ```
+    pub fn eval_with_gradient(&self, x: f64, y: f64) -> (f64, Vec2<f64>) {
+        let result = self.0.evaluate(&[
+            Dual::<2>::variable(x, 0),
+            Dual::variable(y, 1),
+            Dual::constant(0.),
+        ]);
+        (
+            result.value,
+            Vec2 {
+                x: result.grad[0],
+                y: result.grad[1],
+            },
+        )
+    }
+}
+impl F3D {
+    /// Evaluate F3D and its gradient at a given (x,y,z) in a single pass
+    /// ```
+    /// use ruut_functions::{F3D, Vec3};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("xyz^2").unwrap();
+    ///
+    /// assert_eq!(
+    ///     func.eval_with_gradient(1., 2., 3.),
+    ///     (18., Vec3 { x: 18., y: 9., z: 12. })
+    /// );
+    /// ```
+    pub fn eval_with_gradient(&self, x: f64, y: f64, z: f64) -> (f64, Vec3<f64>) {
+        let result = self.0.evaluate(&[
+            Dual::<3>::variable(x, 0),
+            Dual::variable(y, 1),
+            Dual::variable(z, 2),
+        ]);
+        (
+            result.value,
+            Vec3 {
+                x: result.grad[0],
+                y: result.grad[1],
+                z: result.grad[2],
+            },
+        )
+    }
+}
+
+#[test]
+fn test_forward() {
+    use crate::approx;
+    use std::str::FromStr;
+
+    let funcs = [
+        "x^x",
+        "ln(x)sin(x)",
+        "tan(x)+cot(x)",
+        "sec(x)csc(x)",
+        "asin(x/2)acos(x/3)",
+        "atan(x)",
+        "sinh(x)cosh(x)",
+        "tanh(x)coth(x)",
+        "sech(x)+csch(x)",
+        "asinh(x)",
+        "acosh(x+1)",
+        "atanh(x/2)",
+        "abs(x-2)",
+        "e^(2x)/(x+1)",
+        "(x+1)^(1/2)",
+    ];
+    for func in funcs {
+        let func = F1D::from_str(func).unwrap();
+        let (value, derivative) = func.eval_with_derivative(0.7);
+        assert_eq!(value, func.eval(0.7));
+        assert_eq!(
+            approx(derivative, 8),
+            approx(func.derivative().eval(0.7), 8),
+            "{func}"
+        );
+    }
+
+    let func = F3D::from_str("xsin(yz)+x^y/z").unwrap();
+    let (value, gradient) = func.eval_with_gradient(1.5, 0.5, 2.);
+    let expected = func.derivative();
+    assert_eq!(value, func.eval(1.5, 0.5, 2.));
+    assert_eq!(
+        approx(gradient.x, 8),
+        approx(expected.x.eval(1.5, 0.5, 2.), 8)
+    );
+    assert_eq!(
+        approx(gradient.y, 8),
+        approx(expected.y.eval(1.5, 0.5, 2.), 8)
+    );
+    assert_eq!(
+        approx(gradient.z, 8),
+        approx(expected.z.eval(1.5, 0.5, 2.), 8)
+    );
+}
@@ -0,0 +1,4 @@
+mod forward;
+mod reverse;
+pub use forward::Dual;
+pub use reverse::Tape;
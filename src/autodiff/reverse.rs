@@ -0,0 +1,270 @@
+use super::Dual;
+use crate::dag::{Dag, Node as DagNode};
+use crate::operations::{eval_ops, eval_trascendental};
+use crate::scalar::Scalar;
+use crate::{Function, FunctionType, Operation, F1D, F2D, F3D};
+
+#[derive(Debug)]
+enum Node {
+    Variable(usize),
+    Constant(f64),
+    Binary {
+        operation: Operation,
+        terms: (usize, usize),
+    },
+    Special {
+        kind: FunctionType,
+        argument: usize,
+    },
+}
+
+#[derive(Debug)]
+/// Recording of the operations of a function, used to compute gradients in reverse mode.
+/// Identical subexpressions are recorded only once
+pub struct Tape {
+    nodes: Vec<Node>,
+    n_vars: usize,
+}
+
+impl Tape {
+    fn new(func: &Function, n_vars: usize) -> Self {
+        // The dag stores every node after its children, so the root comes last
+        let mut dag = Dag::new();
+        dag.insert(func);
+        let nodes = dag
+            .nodes()
+            .iter()
+            .map(|node| match node {
+                DagNode::X => Node::Variable(0),
+                DagNode::Y => Node::Variable(1),
+                DagNode::Z => Node::Variable(2),
+                DagNode::E => Node::Constant(std::f64::consts::E),
+                DagNode::PI => Node::Constant(std::f64::consts::PI),
+                DagNode::Rational(val) => Node::Constant(val.eval()),
+                DagNode::Binary { operation, terms } => Node::Binary {
+                    operation: operation.clone(),
+                    terms: (terms.0 .0, terms.1 .0),
+                },
+                DagNode::Special { kind, argument } => Node::Special {
+                    kind: kind.clone(),
+                    argument: argument.0,
+                },
+            })
+            .collect();
+
+        Tape { nodes, n_vars }
+    }
+
+    /// Forward pass carrying the directional derivative along `direction`, followed by the
+    /// reverse pass. The adjoints are dual numbers too: their value is the gradient and their
+    /// derivative is the hessian-vector product
+    fn sweep(&self, point: &[f64], direction: &[f64]) -> (f64, Vec<Dual<1>>) {
+        assert_eq!(point.len(), self.n_vars, "Point has the wrong dimension");
+        assert_eq!(
+            direction.len(),
+            self.n_vars,
+            "Direction has the wrong dimension"
+        );
+
+        let mut values: Vec<Dual<1>> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let value = match node {
+                Node::Variable(i) => Dual {
+                    value: point[*i],
+                    grad: [direction[*i]],
+                },
+                Node::Constant(val) => Dual::constant(*val),
+                Node::Binary { operation, terms } => {
+                    eval_ops(operation, values[terms.0], values[terms.1])
+                }
+                Node::Special { kind, argument } => eval_trascendental(kind, values[*argument]),
+            };
+            values.push(value);
+        }
+
+        let zero = Dual::constant(0.);
+        let mut adjoints = vec![zero; self.nodes.len()];
+        adjoints[self.nodes.len() - 1] = Dual::constant(1.);
+
+        for (idx, node) in self.nodes.iter().enumerate().rev() {
+            let adjoint = adjoints[idx];
+            if adjoint == zero {
+                continue;
+            }
+
+            match node {
+                Node::Binary { operation, terms } => {
+                    let (left, right) =
+                        binary_partials(operation, values[terms.0], values[terms.1]);
+                    adjoints[terms.0] = adjoints[terms.0] + adjoint * left;
+                    adjoints[terms.1] = adjoints[terms.1] + adjoint * right;
+                }
+                Node::Special { kind, argument } => {
+                    adjoints[*argument] =
+                        adjoints[*argument] + adjoint * special_partial(kind, values[*argument]);
+                }
+                Node::Variable(_) | Node::Constant(_) => {}
+            }
+        }
+
+        let mut gradient = vec![zero; self.n_vars];
+        for (idx, node) in self.nodes.iter().enumerate() {
+            if let Node::Variable(i) = node {
+                gradient[*i] = adjoints[idx];
+            }
+        }
+
+        (values[self.nodes.len() - 1].value, gradient)
+    }
+
+    /// Computes value and gradient of the recorded function at the given point
+    /// ```
+    /// use ruut_functions::F3D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("xyz^2").unwrap();
+    /// let tape = func.tape();
+    ///
+    /// assert_eq!(tape.gradient(&[1., 2., 3.]), (18., vec![18., 9., 12.]));
+    /// ```
+    pub fn gradient(&self, point: &[f64]) -> (f64, Vec<f64>) {
+        let (value, gradient) = self.sweep(point, &vec![0.; self.n_vars]);
+        (
+            value,
+            gradient.iter().map(|adjoint| adjoint.value).collect(),
+        )
+    }
+
+    /// Computes the product between the hessian at the given point and a direction
+    /// ```
+    /// use ruut_functions::F2D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("x^2y").unwrap();
+    /// let tape = func.tape();
+    ///
+    /// // Hessian at (1,3) is [[6, 2], [2, 0]]
+    /// assert_eq!(tape.hessian_vector_product(&[1., 3.], &[1., 1.]), vec![8., 2.]);
+    /// ```
+    pub fn hessian_vector_product(&self, point: &[f64], direction: &[f64]) -> Vec<f64> {
+        let (_, gradient) = self.sweep(point, direction);
+        gradient.iter().map(|adjoint| adjoint.grad[0]).collect()
+    }
+}
+
+fn binary_partials(operation: &Operation, left: Dual<1>, right: Dual<1>) -> (Dual<1>, Dual<1>) {
+    let one = Dual::constant(1.);
+    match operation {
+        Operation::Add => (one, one),
+        Operation::Sub => (one, -one),
+        Operation::Mul => (right, left),
+        Operation::Div => (one / right, -left / (right * right)),
+        Operation::Pow => (right * left.pow(right - one), left.pow(right) * left.ln()),
+        Operation::Comp => panic!("Something went wrong"),
+    }
+}
+
+fn special_partial(kind: &FunctionType, arg: Dual<1>) -> Dual<1> {
+    let one = Dual::constant(1.);
+    let inv_sqrt = |val: Dual<1>| val.pow(Dual::constant(-0.5));
+
+    match kind {
+        FunctionType::Ln => one / arg,
+        FunctionType::Sin => arg.cos(),
+        FunctionType::Cos => -arg.sin(),
+        FunctionType::Tan => arg.sec() * arg.sec(),
+        FunctionType::Cot => -arg.csc() * arg.csc(),
+        FunctionType::Sec => arg.sec() * arg.tan(),
+        FunctionType::Csc => -arg.csc() * arg.cot(),
+        FunctionType::ASin => inv_sqrt(one - arg * arg),
+        FunctionType::ACos => -inv_sqrt(one - arg * arg),
+        FunctionType::ATan => one / (one + arg * arg),
+        FunctionType::Sinh => arg.cosh(),
+        FunctionType::Cosh => arg.sinh(),
+        FunctionType::Tanh => arg.sech() * arg.sech(),
+        FunctionType::Coth => -arg.csch() * arg.csch(),
+        FunctionType::Sech => -arg.sech() * arg.tanh(),
+        FunctionType::Csch => -arg.csch() * arg.coth(),
+        FunctionType::ASinh => inv_sqrt(arg * arg + one),
+        FunctionType::ACosh => inv_sqrt(arg * arg - one),
+        FunctionType::ATanh => one / (one - arg * arg),
+        FunctionType::Abs => Dual::constant(arg.value.signum()),
+    }
+}
+
+impl F1D {
+    /// Records the function on a tape for reverse mode differentiation
+    pub fn tape(&self) -> Tape {
+        Tape::new(&self.0, 1)
+    }
+}
+impl F2D {
+    /// Records the function on a tape for reverse mode differentiation
+    pub fn tape(&self) -> Tape {
+        Tape::new(&self.0, 2)
+    }
+}
+impl F3D {
+    /// Records the function on a tape for reverse mode differentiation
+    pub fn tape(&self) -> Tape {
+        Tape::new(&self.0, 3)
+    }
+}
+
+#[test]
+fn test_reverse() {
+    use crate::approx;
+    use std::str::FromStr;
+
+    let funcs = [
+        "3x^2+y^4+xyz^2",
+        "sin(xy)cos(yz)+e^(xz)",
+        "ln(x)/y+z^x",
+        "atan(xy)tanh(z)+asinh(x+z)",
+        "(x+y+z)^3/(1+x^2)",
+        "sec(x)csc(y)cot(z)+sech(x)csch(y)coth(z)",
+    ];
+    let point = [0.7, 1.3, 0.4];
+    let direction = [0.5, -1., 2.];
+
+    for func in funcs {
+        let func = F3D::from_str(func).unwrap();
+        let tape = func.tape();
+        let (value, gradient) = tape.gradient(&point);
+        let hvp = tape.hessian_vector_product(&point, &direction);
+
+        assert_eq!(approx(value, 8), approx(func.eval(0.7, 1.3, 0.4), 8));
+
+        let expected = func.derivative();
+        let expected = [
+            expected.x.eval(0.7, 1.3, 0.4),
+            expected.y.eval(0.7, 1.3, 0.4),
+            expected.z.eval(0.7, 1.3, 0.4),
+        ];
+        let hessian = func.hessian().eval(0.7, 1.3, 0.4);
+        for i in 0..3 {
+            assert_eq!(approx(gradient[i], 8), approx(expected[i], 8), "{func}");
+
+            let row: f64 = (0..3)
+                .map(|j| hessian.get(i + 1, j + 1) * direction[j])
+                .sum();
+            assert_eq!(approx(hvp[i], 6), approx(row, 6), "{func}");
+        }
+    }
+
+    // Shared subexpressions are recorded once
+    let func = F1D::from_str("sin(x^2)+cos(x^2)").unwrap();
+    assert_eq!(func.tape().nodes.len(), 6);
+
+    // Deep expressions record one node per distinct subexpression
+    let mut func = Function::X;
+    for _ in 0..300 {
+        func = Function::Special {
+            kind: FunctionType::Sin,
+            argument: Box::new(func),
+        } + Function::X;
+    }
+    let tape = F1D(func).tape();
+    assert_eq!(tape.nodes.len(), 601);
+    assert!(tape.gradient(&[0.5]).0.is_finite());
+}
@@ -0,0 +1,422 @@
+use crate::algebra::rational::Rational;
+use crate::dag::{Dag, Node, NodeId};
+use crate::{FunctionType, Matrix, Operation, Vec2, Vec3, F1D, F2D, F3D};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// Target languages for code generation
+pub enum Language {
+    /// Rust function working on f64
+    Rust,
+    /// C function working on double, using math.h
+    C,
+    /// GLSL function working on float
+    Glsl,
+}
+
+/// Shape of the generated function's output
+enum Shape {
+    Scalar,
+    Vector(usize),
+    Matrix(usize, usize),
+}
+
+struct Generator<'a> {
+    dag: &'a Dag,
+    lang: Language,
+    locals: Vec<Option<String>>,
+}
+
+impl Generator<'_> {
+    fn number(&self, val: f64) -> String {
+        let literal = match self.lang {
+            Language::Rust => format!("{:?}_f64", val.abs()),
+            Language::C | Language::Glsl => format!("{:?}", val.abs()),
+        };
+        if val < 0. {
+            format!("(-{literal})")
+        } else {
+            literal
+        }
+    }
+
+    fn rational(&self, val: &Rational) -> String {
+        if val.den() == 1 {
+            self.number(val.num() as f64)
+        } else {
+            format!(
+                "({} / {})",
+                self.number(val.num() as f64),
+                self.number(val.den() as f64)
+            )
+        }
+    }
+
+    fn expr(&self, id: NodeId) -> String {
+        if let Some(local) = &self.locals[id.0] {
+            return local.clone();
+        }
+
+        match self.dag.node(id) {
+            Node::X => "x".to_string(),
+            Node::Y => "y".to_string(),
+            Node::Z => "z".to_string(),
+            Node::E => self.number(std::f64::consts::E),
+            Node::PI => self.number(std::f64::consts::PI),
+            Node::Rational(val) => self.rational(val),
+            Node::Binary { operation, terms } => {
+                let left = self.expr(terms.0);
+                let right = self.expr(terms.1);
+
+                match operation {
+                    Operation::Add => format!("({left} + {right})"),
+                    Operation::Sub => format!("({left} - {right})"),
+                    Operation::Mul => format!("({left} * {right})"),
+                    Operation::Div => format!("({left} / {right})"),
+                    Operation::Pow => self.pow(left, terms.1, right),
+                    Operation::Comp => panic!("Something went wrong"),
+                }
+            }
+            Node::Special { kind, argument } => self.special(kind, self.expr(*argument)),
+        }
+    }
+
+    fn pow(&self, base: String, exp_id: NodeId, exp: String) -> String {
+        let integer = match self.dag.node(exp_id) {
+            Node::Rational(val) if val.den() == 1 => Some(val.num()),
+            _ => None,
+        };
+
+        match (self.lang, integer) {
+            (Language::Rust, Some(n)) => format!("{base}.powi({n})"),
+            (Language::Rust, None) => format!("{base}.powf({exp})"),
+            (Language::C, _) => format!("pow({base}, {exp})"),
+            // GLSL's pow is undefined for negative bases
+            (Language::Glsl, Some(n)) if n % 2 == 0 => format!("pow(abs({base}), {exp})"),
+            (Language::Glsl, Some(_)) => format!("(sign({base}) * pow(abs({base}), {exp}))"),
+            (Language::Glsl, None) => format!("pow({base}, {exp})"),
+        }
+    }
+
+    fn special(&self, kind: &FunctionType, arg: String) -> String {
+        let (name, reciprocal) = match kind {
+            FunctionType::Sin => ("sin", false),
+            FunctionType::Cos => ("cos", false),
+            FunctionType::Tan => ("tan", false),
+            FunctionType::Cot => ("tan", true),
+            FunctionType::Sec => ("cos", true),
+            FunctionType::Csc => ("sin", true),
+            FunctionType::ASin => ("asin", false),
+            FunctionType::ACos => ("acos", false),
+            FunctionType::ATan => ("atan", false),
+            FunctionType::Sinh => ("sinh", false),
+            FunctionType::Cosh => ("cosh", false),
+            FunctionType::Tanh => ("tanh", false),
+            FunctionType::Coth => ("tanh", true),
+            FunctionType::Sech => ("cosh", true),
+            FunctionType::Csch => ("sinh", true),
+            FunctionType::ASinh => ("asinh", false),
+            FunctionType::ACosh => ("acosh", false),
+            FunctionType::ATanh => ("atanh", false),
+            FunctionType::Abs => match self.lang {
+                Language::C => ("fabs", false),
+                _ => ("abs", false),
+            },
+            FunctionType::Ln => match self.lang {
+                Language::Rust => ("ln", false),
+                _ => ("log", false),
+            },
+        };
+
+        let call = match self.lang {
+            Language::Rust => format!("{arg}.{name}()"),
+            Language::C | Language::Glsl => format!("{name}({arg})"),
+        };
+        if reciprocal {
+            format!("({} / {call})", self.number(1.))
+        } else {
+            call
+        }
+    }
+}
+
+/// Nodes used more than once become local variables, leaves are always inlined
+fn shared_nodes(dag: &Dag, roots: &[NodeId]) -> Vec<NodeId> {
+    let needed = dag.reachable(roots);
+    let mut uses = vec![0; needed.len()];
+    for root in roots {
+        uses[root.0] += 1;
+    }
+    for (idx, _) in needed.iter().enumerate().filter(|(_, needed)| **needed) {
+        match dag.node(NodeId(idx)) {
+            Node::Binary { terms, .. } => {
+                uses[terms.0 .0] += 1;
+                uses[terms.1 .0] += 1;
+            }
+            Node::Special { argument, .. } => uses[argument.0] += 1,
+            _ => {}
+        }
+    }
+
+    (0..needed.len())
+        .map(NodeId)
+        .filter(|id| {
+            uses[id.0] > 1 && matches!(dag.node(*id), Node::Binary { .. } | Node::Special { .. })
+        })
+        .collect()
+}
+
+fn generate(
+    dag: &Dag,
+    roots: &[NodeId],
+    shape: Shape,
+    lang: Language,
+    name: &str,
+    vars: &[&str],
+) -> String {
+    let mut generator = Generator {
+        dag,
+        lang,
+        locals: vec![None; dag.len()],
+    };
+
+    let mut body = String::new();
+    for (i, id) in shared_nodes(dag, roots).into_iter().enumerate() {
+        let expr = generator.expr(id);
+        let local = format!("t{i}");
+        body += &match lang {
+            Language::Rust => format!("    let {local} = {expr};\n"),
+            Language::C => format!("    const double {local} = {expr};\n"),
+            Language::Glsl => format!("    float {local} = {expr};\n"),
+        };
+        generator.locals[id.0] = Some(local);
+    }
+    let exprs: Vec<String> = roots.iter().map(|id| generator.expr(*id)).collect();
+
+    let scalar = match lang {
+        Language::Rust => "f64",
+        Language::C => "double",
+        Language::Glsl => "float",
+    };
+    let params: Vec<String> = vars
+        .iter()
+        .map(|var| match lang {
+            Language::Rust => format!("{var}: f64"),
+            _ => format!("{scalar} {var}"),
+        })
+        .collect();
+    let params = params.join(", ");
+
+    match lang {
+        Language::Rust => {
+            let (output, result) = match shape {
+                Shape::Scalar => ("f64".to_string(), exprs[0].clone()),
+                Shape::Vector(n) => (format!("[f64; {n}]"), format!("[{}]", exprs.join(", "))),
+                Shape::Matrix(n_row, n_col) => {
+                    let rows: Vec<String> = exprs
+                        .chunks(n_col)
+                        .map(|row| format!("[{}]", row.join(", ")))
+                        .collect();
+                    (
+                        format!("[[f64; {n_col}]; {n_row}]"),
+                        format!("[{}]", rows.join(", ")),
+                    )
+                }
+            };
+            format!("pub fn {name}({params}) -> {output} {{\n{body}    {result}\n}}\n")
+        }
+        Language::C => match shape {
+            Shape::Scalar => {
+                format!(
+                    "double {name}({params}) {{\n{body}    return {};\n}}\n",
+                    exprs[0]
+                )
+            }
+            Shape::Vector(n) => {
+                for (i, expr) in exprs.iter().enumerate() {
+                    body += &format!("    out[{i}] = {expr};\n");
+                }
+                format!("void {name}({params}, double out[{n}]) {{\n{body}}}\n")
+            }
+            Shape::Matrix(n_row, n_col) => {
+                for (i, expr) in exprs.iter().enumerate() {
+                    body += &format!("    out[{}][{}] = {expr};\n", i / n_col, i % n_col);
+                }
+                format!("void {name}({params}, double out[{n_row}][{n_col}]) {{\n{body}}}\n")
+            }
+        },
+        Language::Glsl => {
+            let (output, result) = match shape {
+                Shape::Scalar => ("float".to_string(), exprs[0].clone()),
+                Shape::Vector(n) => {
+                    let output = format!("vec{n}");
+                    let result = format!("{output}({})", exprs.join(", "));
+                    (output, result)
+                }
+                Shape::Matrix(n_row, n_col) => {
+                    assert!(
+                        (2..=4).contains(&n_row) && (2..=4).contains(&n_col),
+                        "GLSL only supports matrices from 2x2 to 4x4"
+                    );
+                    let output = if n_row == n_col {
+                        format!("mat{n_row}")
+                    } else {
+                        format!("mat{n_col}x{n_row}")
+                    };
+                    // GLSL matrices are built column by column
+                    let columns: Vec<&str> = (0..n_col)
+                        .flat_map(|col| (0..n_row).map(move |row| (row, col)))
+                        .map(|(row, col)| exprs[row * n_col + col].as_str())
+                        .collect();
+                    let result = format!("{output}({})", columns.join(", "));
+                    (output, result)
+                }
+            };
+            format!("{output} {name}({params}) {{\n{body}    return {result};\n}}\n")
+        }
+    }
+}
+
+impl F1D {
+    /// Generates the source code of a function computing F1D in the given language
+    /// ```
+    /// use ruut_functions::{F1D, codegen::Language};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("sin(x^2)+cos(x^2)").unwrap();
+    ///
+    /// assert_eq!(
+    ///     func.to_source(Language::C, "f"),
+    ///     "double f(double x) {\n    const double t0 = pow(x, 2.0);\n    return (sin(t0) + cos(t0));\n}\n"
+    /// );
+    /// ```
+    pub fn to_source(&self, lang: Language, name: &str) -> String {
+        let mut dag = Dag::new();
+        let root = dag.insert_f1d(self);
+        generate(&dag, &[root], Shape::Scalar, lang, name, &["x"])
+    }
+}
+impl F2D {
+    /// Generates the source code of a function computing F2D in the given language
+    /// ```
+    /// use ruut_functions::{F2D, codegen::Language};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("xy+1").unwrap();
+    ///
+    /// assert_eq!(
+    ///     func.to_source(Language::Rust, "f"),
+    ///     "pub fn f(x: f64, y: f64) -> f64 {\n    ((x * y) + 1.0_f64)\n}\n"
+    /// );
+    /// ```
+    pub fn to_source(&self, lang: Language, name: &str) -> String {
+        let mut dag = Dag::new();
+        let root = dag.insert_f2d(self);
+        generate(&dag, &[root], Shape::Scalar, lang, name, &["x", "y"])
+    }
+}
+impl F3D {
+    /// Generates the source code of a function computing F3D in the given language
+    /// ```
+    /// use ruut_functions::{F3D, codegen::Language};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("xyz").unwrap();
+    ///
+    /// assert_eq!(
+    ///     func.to_source(Language::Glsl, "f"),
+    ///     "float f(float x, float y, float z) {\n    return ((x * y) * z);\n}\n"
+    /// );
+    /// ```
+    pub fn to_source(&self, lang: Language, name: &str) -> String {
+        let mut dag = Dag::new();
+        let root = dag.insert_f3d(self);
+        generate(&dag, &[root], Shape::Scalar, lang, name, &["x", "y", "z"])
+    }
+}
+
+impl Vec2<F2D> {
+    /// Generates the source code of a function computing both components, useful for gradients
+    pub fn to_source(&self, lang: Language, name: &str) -> String {
+        let mut dag = Dag::new();
+        let roots = [dag.insert_f2d(&self.x), dag.insert_f2d(&self.y)];
+        generate(&dag, &roots, Shape::Vector(2), lang, name, &["x", "y"])
+    }
+}
+impl Vec3<F3D> {
+    /// Generates the source code of a function computing all three components, useful for
+    /// gradients
+    pub fn to_source(&self, lang: Language, name: &str) -> String {
+        let mut dag = Dag::new();
+        let roots = [
+            dag.insert_f3d(&self.x),
+            dag.insert_f3d(&self.y),
+            dag.insert_f3d(&self.z),
+        ];
+        generate(&dag, &roots, Shape::Vector(3), lang, name, &["x", "y", "z"])
+    }
+}
+impl Matrix<F2D> {
+    /// Generates the source code of a function computing every entry, useful for hessians
+    pub fn to_source(&self, lang: Language, name: &str) -> String {
+        let mut dag = Dag::new();
+        let roots: Vec<NodeId> = self.as_slice().iter().map(|f| dag.insert_f2d(f)).collect();
+        let shape = Shape::Matrix(self.n_row(), self.n_col());
+        generate(&dag, &roots, shape, lang, name, &["x", "y"])
+    }
+}
+impl Matrix<F3D> {
+    /// Generates the source code of a function computing every entry, useful for hessians
+    /// ```
+    /// use ruut_functions::{F3D, codegen::Language};
+    /// use std::str::FromStr;
+    ///
+    /// let hessian = F3D::from_str("x^2y+z").unwrap().hessian();
+    /// let source = hessian.to_source(Language::Glsl, "hessian");
+    ///
+    /// assert!(source.starts_with("mat3 hessian(float x, float y, float z) {"));
+    /// ```
+    pub fn to_source(&self, lang: Language, name: &str) -> String {
+        let mut dag = Dag::new();
+        let roots: Vec<NodeId> = self.as_slice().iter().map(|f| dag.insert_f3d(f)).collect();
+        let shape = Shape::Matrix(self.n_row(), self.n_col());
+        generate(&dag, &roots, shape, lang, name, &["x", "y", "z"])
+    }
+}
+
+#[test]
+fn test_codegen() {
+    use std::str::FromStr;
+
+    let func = F1D::from_str("x^3+sec(x)/ln(x)").unwrap();
+    assert_eq!(
+        func.to_source(Language::Rust, "f"),
+        "pub fn f(x: f64) -> f64 {\n    (x.powi(3) + ((1.0_f64 / x.cos()) / x.ln()))\n}\n"
+    );
+    assert_eq!(
+        func.to_source(Language::Glsl, "f"),
+        "float f(float x) {\n    return ((sign(x) * pow(abs(x), 3.0)) + ((1.0 / cos(x)) / log(x)));\n}\n"
+    );
+
+    let gradient = F2D::from_str("e^(xy)").unwrap().derivative();
+    assert_eq!(
+        gradient.to_source(Language::C, "grad"),
+        "void grad(double x, double y, double out[2]) {\n    const double t0 = pow(2.718281828459045, (x * y));\n    out[0] = (y * t0);\n    out[1] = (x * t0);\n}\n"
+    );
+
+    let hessian = Matrix::new(
+        ["x", "-y/2", "1", "2"]
+            .iter()
+            .map(|f| F2D::from_str(f).unwrap())
+            .collect(),
+        2,
+        2,
+    );
+    assert_eq!(
+        hessian.to_source(Language::Rust, "h"),
+        "pub fn h(x: f64, y: f64) -> [[f64; 2]; 2] {\n    [[x, (((-1.0_f64) * y) / 2.0_f64)], [1.0_f64, 2.0_f64]]\n}\n"
+    );
+    assert_eq!(
+        hessian.to_source(Language::Glsl, "h"),
+        "mat2 h(float x, float y) {\n    return mat2(x, 1.0, (((-1.0) * y) / 2.0), 2.0);\n}\n"
+    );
+}
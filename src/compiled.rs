@@ -0,0 +1,216 @@
+use crate::dag::{Dag, Node, NodeId};
+use crate::operations::{eval_ops, eval_trascendental};
+use crate::{FunctionType, Operation, F1D, F2D, F3D};
+use std::cell::RefCell;
+
+#[derive(Debug, Clone)]
+/// Single step of a program, operands are indices of previous steps
+pub(crate) enum Instr {
+    Var(usize),
+    Const(f64),
+    Binary(Operation, usize, usize),
+    Powi(usize, i32),
+    Special(FunctionType, usize),
+}
+
+#[derive(Clone, Copy)]
+enum Slot {
+    Const(f64),
+    Instr(usize),
+}
+
+#[derive(Debug, Clone)]
+/// Flat, constant-folded form of a function. Shared subexpressions are computed once and the
+/// last instruction is the result
+pub(crate) struct Program {
+    pub(crate) instrs: Vec<Instr>,
+}
+
+impl Program {
+    fn new(dag: &Dag, root: NodeId) -> Self {
+        let needed = dag.reachable(&[root]);
+        let mut program = Program { instrs: Vec::new() };
+        // Value of each node: either folded into a constant or computed by an instruction
+        let mut slots = vec![Slot::Const(0.); dag.len()];
+
+        for idx in (0..dag.len()).filter(|idx| needed[*idx]) {
+            slots[idx] = match dag.node(NodeId(idx)) {
+                Node::X => Slot::Instr(program.push(Instr::Var(0))),
+                Node::Y => Slot::Instr(program.push(Instr::Var(1))),
+                Node::Z => Slot::Instr(program.push(Instr::Var(2))),
+                Node::E => Slot::Const(std::f64::consts::E),
+                Node::PI => Slot::Const(std::f64::consts::PI),
+                Node::Rational(val) => Slot::Const(val.eval()),
+                Node::Binary { operation, terms } => match (slots[terms.0 .0], slots[terms.1 .0]) {
+                    (Slot::Const(l), Slot::Const(r)) => Slot::Const(eval_ops(operation, l, r)),
+                    (base, Slot::Const(r))
+                        if *operation == Operation::Pow
+                            && r.fract() == 0.
+                            && r.abs() <= i32::MAX as f64 =>
+                    {
+                        let base = program.operand(&mut slots, terms.0 .0, base);
+                        Slot::Instr(program.push(Instr::Powi(base, r as i32)))
+                    }
+                    (left, right) => {
+                        let left = program.operand(&mut slots, terms.0 .0, left);
+                        let right = program.operand(&mut slots, terms.1 .0, right);
+                        let instr = Instr::Binary(operation.clone(), left, right);
+                        Slot::Instr(program.push(instr))
+                    }
+                },
+                Node::Special { kind, argument } => match slots[argument.0] {
+                    Slot::Const(val) => Slot::Const(eval_trascendental(kind, val)),
+                    arg => {
+                        let arg = program.operand(&mut slots, argument.0, arg);
+                        Slot::Instr(program.push(Instr::Special(kind.clone(), arg)))
+                    }
+                },
+            };
+        }
+
+        let result = slots[root.0];
+        program.operand(&mut slots, root.0, result);
+        program
+    }
+
+    fn push(&mut self, instr: Instr) -> usize {
+        self.instrs.push(instr);
+        self.instrs.len() - 1
+    }
+
+    /// Index of the instruction computing the node, constants get one the first time they are
+    /// used by an instruction
+    fn operand(&mut self, slots: &mut [Slot], idx: usize, slot: Slot) -> usize {
+        match slot {
+            Slot::Instr(instr) => instr,
+            Slot::Const(val) => {
+                let instr = self.push(Instr::Const(val));
+                slots[idx] = Slot::Instr(instr);
+                instr
+            }
+        }
+    }
+
+    pub(crate) fn eval(&self, inputs: &[f64; 3]) -> f64 {
+        SCRATCH.with(|values| {
+            let mut values = values.borrow_mut();
+            values.clear();
+            for instr in &self.instrs {
+                let value = match instr {
+                    Instr::Var(i) => inputs[*i],
+                    Instr::Const(val) => *val,
+                    Instr::Binary(operation, left, right) => {
+                        eval_ops(operation, values[*left], values[*right])
+                    }
+                    Instr::Powi(base, exp) => values[*base].powi(*exp),
+                    Instr::Special(kind, arg) => eval_trascendental(kind, values[*arg]),
+                };
+                values.push(value);
+            }
+
+            values[values.len() - 1]
+        })
+    }
+}
+
+thread_local! {
+    /// Values of the instructions, reused across calls so that evaluating doesn't allocate
+    static SCRATCH: RefCell<Vec<f64>> = const { RefCell::new(Vec::new()) };
+}
+
+impl F1D {
+    pub(crate) fn program(&self) -> Program {
+        let mut dag = Dag::new();
+        let root = dag.insert_f1d(self);
+        Program::new(&dag, root)
+    }
+
+    /// Converts the function into a closure, constant subexpressions are evaluated once
+    /// ```
+    /// use ruut_functions::F1D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("x^2+sin(pi/2)").unwrap().into_fn();
+    ///
+    /// assert_eq!(func(3.), 10.);
+    /// ```
+    pub fn into_fn(self) -> impl Fn(f64) -> f64 + Send + Sync {
+        let program = self.program();
+        move |x| program.eval(&[x, 0., 0.])
+    }
+}
+impl F2D {
+    pub(crate) fn program(&self) -> Program {
+        let mut dag = Dag::new();
+        let root = dag.insert_f2d(self);
+        Program::new(&dag, root)
+    }
+
+    /// Converts the function into a closure, constant subexpressions are evaluated once
+    /// ```
+    /// use ruut_functions::F2D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("xy+ln(e)").unwrap().into_fn();
+    ///
+    /// assert_eq!(func(2., 3.), 7.);
+    /// ```
+    pub fn into_fn(self) -> impl Fn(f64, f64) -> f64 + Send + Sync {
+        let program = self.program();
+        move |x, y| program.eval(&[x, y, 0.])
+    }
+}
+impl F3D {
+    pub(crate) fn program(&self) -> Program {
+        let mut dag = Dag::new();
+        let root = dag.insert_f3d(self);
+        Program::new(&dag, root)
+    }
+
+    /// Converts the function into a closure, constant subexpressions are evaluated once
+    /// ```
+    /// use ruut_functions::F3D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("xyz-1").unwrap().into_fn();
+    ///
+    /// assert_eq!(func(2., 3., 4.), 23.);
+    /// ```
+    pub fn into_fn(self) -> impl Fn(f64, f64, f64) -> f64 + Send + Sync {
+        let program = self.program();
+        move |x, y, z| program.eval(&[x, y, z])
+    }
+}
+
+#[test]
+fn test_compiled() {
+    use std::str::FromStr;
+
+    let func = F1D::from_str("(2+3)^2x+sin(pi)cos(x)+x^(-3)").unwrap();
+    let program = func.program();
+    // x, 25, 25x, sin(pi) (folded), cos(x), sin(pi)cos(x), sum, x^-3, result
+    assert_eq!(program.instrs.len(), 9);
+    assert!(matches!(program.instrs[7], Instr::Powi(0, -3)));
+
+    let funcs = [
+        "sin(xy)^2+cos(xy)^2+z^(1/2)",
+        "e^(x+y)/(1+z^2)",
+        "abs(x-y)ln(z)+atan(x)",
+    ];
+    for func in funcs {
+        let func = F3D::from_str(func).unwrap();
+        let expected = func.eval(0.4, -1.3, 2.5);
+        let closure = func.into_fn();
+        let handle = std::thread::spawn(move || closure(0.4, -1.3, 2.5));
+        let value = handle.join().unwrap();
+        assert!((value - expected).abs() < 1e-12 * expected.abs().max(1.));
+    }
+
+    // Closures of different sizes share the scratch buffer of the thread
+    let long = F1D::from_str("sin(x)^2+cos(x)^2+x^3").unwrap().into_fn();
+    let short = F1D::from_str("2x").unwrap().into_fn();
+    for x in [0.5, -1., 3.] {
+        assert!((long(x) - 1. - x * x * x).abs() < 1e-12);
+        assert_eq!(short(x), 2. * x);
+    }
+}
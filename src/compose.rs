@@ -0,0 +1,134 @@
+use crate::{Function, F1D, F2D, F3D};
+
+impl Function {
+    /// Replaces x, y and z with the given functions at the same time
+    fn substitute(&self, x: &Self, y: &Self, z: &Self) -> Self {
+        match self {
+            Self::X => x.clone(),
+            Self::Y => y.clone(),
+            Self::Z => z.clone(),
+            Self::E | Self::PI | Self::Rational(_) => self.clone(),
+            Self::Binary { operation, terms } => Self::Binary {
+                operation: operation.clone(),
+                terms: (
+                    Box::new(terms.0.substitute(x, y, z)),
+                    Box::new(terms.1.substitute(x, y, z)),
+                ),
+            },
+            Self::Special { kind, argument } => Self::Special {
+                kind: kind.clone(),
+                argument: Box::new(argument.substitute(x, y, z)),
+            },
+        }
+    }
+}
+
+impl F1D {
+    /// Computes self(inner(x))
+    /// ```
+    /// use ruut_functions::F1D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("x^2+1").unwrap();
+    /// let inner = F1D::from_str("x+1").unwrap();
+    ///
+    /// assert_eq!(func.compose(&inner).to_string(), "(x+1)^2+1");
+    /// ```
+    pub fn compose(&self, inner: &F1D) -> Self {
+        F1D(self
+            .0
+            .substitute(&inner.0, &Function::Y, &Function::Z)
+            .simplify())
+    }
+}
+
+impl F2D {
+    /// Replaces x with the given function
+    /// ```
+    /// use ruut_functions::F2D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("xy").unwrap();
+    /// let x = F2D::from_str("x-y").unwrap();
+    ///
+    /// assert_eq!(func.substitute_x(&x).to_string(), "xy-y^2");
+    /// ```
+    pub fn substitute_x(&self, x: &F2D) -> Self {
+        F2D(self
+            .0
+            .substitute(&x.0, &Function::Y, &Function::Z)
+            .simplify())
+    }
+
+    /// Replaces y with the given function
+    /// ```
+    /// use ruut_functions::F2D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("x^y").unwrap();
+    /// let y = F2D::from_str("2y").unwrap();
+    ///
+    /// assert_eq!(func.substitute_y(&y).to_string(), "x^(2y)");
+    /// ```
+    pub fn substitute_y(&self, y: &F2D) -> Self {
+        F2D(self
+            .0
+            .substitute(&Function::X, &y.0, &Function::Z)
+            .simplify())
+    }
+}
+
+impl F3D {
+    /// Replaces x, y and z with the given functions simultaneously
+    /// ```
+    /// use ruut_functions::F3D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("x-y+z").unwrap();
+    /// let (x, y, z) = (
+    ///     F3D::from_str("y").unwrap(),
+    ///     F3D::from_str("x").unwrap(),
+    ///     F3D::from_str("x+y").unwrap(),
+    /// );
+    ///
+    /// assert_eq!(func.substitute(&x, &y, &z).to_string(), "2y");
+    /// ```
+    pub fn substitute(&self, x: &F3D, y: &F3D, z: &F3D) -> Self {
+        F3D(self.0.substitute(&x.0, &y.0, &z.0).simplify())
+    }
+}
+
+#[test]
+fn test_compose() {
+    use std::str::FromStr;
+
+    let cases = [
+        ("x^2", "x+1", "(x+1)^2"),
+        ("1/x", "x-2", "1/(x-2)"),
+        ("sin(x)", "2x^3", "sin(2x^3)"),
+        ("2x", "x/2", "x"),
+        ("x-3", "-x", "-x-3"),
+    ];
+    for (func, inner, expected) in cases {
+        let composed = F1D::from_str(func)
+            .unwrap()
+            .compose(&F1D::from_str(inner).unwrap());
+        assert_eq!(composed.to_string(), expected);
+    }
+
+    let func = F2D::from_str("x/y").unwrap();
+    let func = func
+        .substitute_x(&F2D::from_str("y+1").unwrap())
+        .substitute_y(&F2D::from_str("x").unwrap());
+    assert_eq!(func.to_string(), "1/x+1");
+
+    // Change of variables to cylindrical coordinates
+    let func = F3D::from_str("x^2+y^2+z").unwrap();
+    let cylindrical = func.substitute(
+        &F3D::from_str("xcos(y)").unwrap(),
+        &F3D::from_str("xsin(y)").unwrap(),
+        &F3D::from_str("z").unwrap(),
+    );
+    let (r, theta, z) = (1.5, 0.4, 2.);
+    assert!((cylindrical.eval(r, theta, z) - r * r - z).abs() < 1e-12);
+}
@@ -0,0 +1,582 @@
+use crate::algebra::rational::Rational;
+use crate::operations::{eval_ops, eval_trascendental};
+use crate::scalar::Scalar;
+use crate::{Function, FunctionType, Matrix, Operation, Variable, F1D, F2D, F3D};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// Handle to a node stored in a Dag
+pub struct NodeId(pub(crate) usize);
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub(crate) enum Node {
+    X,
+    Y,
+    Z,
+    E,
+    PI,
+    Rational(Rational),
+    Binary {
+        operation: Operation,
+        terms: (NodeId, NodeId),
+    },
+    Special {
+        kind: FunctionType,
+        argument: NodeId,
+    },
+}
+
+#[derive(Debug, Default)]
+/// Expression graph where identical subexpressions are stored only once (hash consing).
+///
+/// Nodes are immutable and always inserted after their children, so evaluating them in order
+/// computes every shared subexpression once per point.
+/// ```
+/// use ruut_functions::{F3D, Variable, dag::Dag};
+/// use std::str::FromStr;
+///
+/// let func = F3D::from_str("sin(xy)+cos(xy)").unwrap();
+/// let mut dag = Dag::new();
+/// let id = dag.insert_f3d(&func);
+/// let hessian = dag.hessian(id, &[Variable::X, Variable::Y, Variable::Z]);
+///
+/// let values = dag.eval(hessian.as_slice(), &[1., 2., 3.]);
+/// assert_eq!(values.len(), 9);
+/// assert_eq!(dag.to_f3d(id), func);
+/// ```
+pub struct Dag {
+    nodes: Vec<Node>,
+    ids: HashMap<Node, NodeId>,
+    derivatives: HashMap<(NodeId, Variable), NodeId>,
+}
+
+impl Dag {
+    /// Creates an empty dag
+    pub fn new() -> Self {
+        Dag::default()
+    }
+
+    /// Number of distinct nodes stored
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Checks if the dag doesn't contain any node
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub(crate) fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    fn intern(&mut self, node: Node) -> NodeId {
+        if let Some(id) = self.ids.get(&node) {
+            return *id;
+        }
+
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(node.clone());
+        self.ids.insert(node, id);
+        id
+    }
+
+    fn rational(&self, id: NodeId) -> Option<&Rational> {
+        match &self.nodes[id.0] {
+            Node::Rational(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    fn is(&self, id: NodeId, val: i32) -> bool {
+        matches!(self.rational(id), Some(rat) if *rat == val)
+    }
+
+    fn int(&mut self, val: i32) -> NodeId {
+        self.intern(Node::Rational(Rational::new_from_int(val)))
+    }
+
+    fn binary(&mut self, operation: Operation, left: NodeId, right: NodeId) -> NodeId {
+        self.intern(Node::Binary {
+            operation,
+            terms: (left, right),
+        })
+    }
+
+    /// Orders the operands of commutative operations so that a+b and b+a are the same node,
+    /// constants always come first
+    fn sort(&self, left: NodeId, right: NodeId) -> (NodeId, NodeId) {
+        let key = |id: NodeId| (self.rational(id).is_none(), id.0);
+        if key(left) <= key(right) {
+            (left, right)
+        } else {
+            (right, left)
+        }
+    }
+
+    fn fold(
+        &mut self,
+        left: NodeId,
+        right: NodeId,
+        op: fn(Rational, Rational) -> Option<Rational>,
+    ) -> Option<NodeId> {
+        let result = op(self.rational(left)?.clone(), self.rational(right)?.clone())?;
+        Some(self.intern(Node::Rational(result)))
+    }
+
+    fn add(&mut self, left: NodeId, right: NodeId) -> NodeId {
+        if let Some(id) = self.fold(left, right, Rational::checked_add) {
+            return id;
+        }
+        if self.is(left, 0) {
+            return right;
+        }
+        if self.is(right, 0) {
+            return left;
+        }
+        if left == right {
+            let two = self.int(2);
+            return self.mul(two, left);
+        }
+
+        let (left, right) = self.sort(left, right);
+        self.binary(Operation::Add, left, right)
+    }
+
+    fn sub(&mut self, left: NodeId, right: NodeId) -> NodeId {
+        if let Some(id) = self.fold(left, right, Rational::checked_sub) {
+            return id;
+        }
+        if self.is(right, 0) {
+            return left;
+        }
+        if left == right {
+            return self.int(0);
+        }
+        if self.is(left, 0) {
+            let minus_one = self.int(-1);
+            return self.mul(minus_one, right);
+        }
+
+        self.binary(Operation::Sub, left, right)
+    }
+
+    fn mul(&mut self, left: NodeId, right: NodeId) -> NodeId {
+        if let Some(id) = self.fold(left, right, Rational::checked_mul) {
+            return id;
+        }
+        if self.is(left, 0) || self.is(right, 0) {
+            return self.int(0);
+        }
+        if self.is(left, 1) {
+            return right;
+        }
+        if self.is(right, 1) {
+            return left;
+        }
+        if left == right {
+            let two = self.int(2);
+            return self.pow(left, two);
+        }
+
+        let (left, right) = self.sort(left, right);
+        // c1*(c2*f) = (c1c2)*f
+        if let Node::Binary {
+            operation: Operation::Mul,
+            terms,
+        } = self.nodes[right.0].clone()
+        {
+            if let Some(id) = self.fold(left, terms.0, Rational::checked_mul) {
+                return self.mul(id, terms.1);
+            }
+        }
+
+        self.binary(Operation::Mul, left, right)
+    }
+
+    fn div(&mut self, left: NodeId, right: NodeId) -> NodeId {
+        if !self.is(right, 0) {
+            if let Some(id) = self.fold(left, right, Rational::checked_div) {
+                return id;
+            }
+        }
+        if self.is(left, 0) {
+            return self.int(0);
+        }
+        if self.is(right, 1) {
+            return left;
+        }
+        if left == right {
+            return self.int(1);
+        }
+
+        self.binary(Operation::Div, left, right)
+    }
+
+    fn pow(&mut self, base: NodeId, exp: NodeId) -> NodeId {
+        if self.is(exp, 0) {
+            return self.int(1);
+        }
+        if self.is(exp, 1) {
+            return base;
+        }
+        // (f^r1)^n = f^(r1n), only for integer n since (x^2)^(1/2) is |x|
+        let integer_exp = matches!(self.rational(exp), Some(exp) if exp.den() == 1);
+        if let (
+            true,
+            Node::Binary {
+                operation: Operation::Pow,
+                terms,
+            },
+        ) = (integer_exp, self.nodes[base.0].clone())
+        {
+            if let Some(id) = self.fold(terms.1, exp, Rational::checked_mul) {
+                return self.pow(terms.0, id);
+            }
+        }
+
+        self.binary(Operation::Pow, base, exp)
+    }
+
+    fn special(&mut self, kind: FunctionType, argument: NodeId) -> NodeId {
+        self.intern(Node::Special { kind, argument })
+    }
+
+    pub(crate) fn insert(&mut self, func: &Function) -> NodeId {
+        match func {
+            Function::X => self.intern(Node::X),
+            Function::Y => self.intern(Node::Y),
+            Function::Z => self.intern(Node::Z),
+            Function::E => self.intern(Node::E),
+            Function::PI => self.intern(Node::PI),
+            Function::Rational(val) => self.intern(Node::Rational(val.clone())),
+            Function::Binary { operation, terms } => {
+                let left = self.insert(&terms.0);
+                let right = self.insert(&terms.1);
+                // a+b and b+a share a node, the order inserted first is kept
+                if let Operation::Add | Operation::Mul = operation {
+                    let swapped = Node::Binary {
+                        operation: operation.clone(),
+                        terms: (right, left),
+                    };
+                    if let Some(id) = self.ids.get(&swapped) {
+                        return *id;
+                    }
+                }
+                self.binary(operation.clone(), left, right)
+            }
+            Function::Special { kind, argument } => {
+                let argument = self.insert(argument);
+                self.special(kind.clone(), argument)
+            }
+        }
+    }
+
+    /// Inserts a F1D, returning the id of its root
+    pub fn insert_f1d(&mut self, func: &F1D) -> NodeId {
+        self.insert(&func.0)
+    }
+    /// Inserts a F2D, returning the id of its root
+    pub fn insert_f2d(&mut self, func: &F2D) -> NodeId {
+        self.insert(&func.0)
+    }
+    /// Inserts a F3D, returning the id of its root
+    pub fn insert_f3d(&mut self, func: &F3D) -> NodeId {
+        self.insert(&func.0)
+    }
+
+    pub(crate) fn to_function(&self, id: NodeId) -> Function {
+        match &self.nodes[id.0] {
+            Node::X => Function::X,
+            Node::Y => Function::Y,
+            Node::Z => Function::Z,
+            Node::E => Function::E,
+            Node::PI => Function::PI,
+            Node::Rational(val) => Function::Rational(val.clone()),
+            Node::Binary { operation, terms } => Function::Binary {
+                operation: operation.clone(),
+                terms: (
+                    Box::new(self.to_function(terms.0)),
+                    Box::new(self.to_function(terms.1)),
+                ),
+            },
+            Node::Special { kind, argument } => Function::Special {
+                kind: kind.clone(),
+                argument: Box::new(self.to_function(*argument)),
+            },
+        }
+    }
+
+    /// Expands the node back into a F3D
+    pub fn to_f3d(&self, id: NodeId) -> F3D {
+        F3D(self.to_function(id))
+    }
+
+    /// Computes the derivative of the node with respect to the given variable. Derivatives are
+    /// cached, so asking again for the same node (or a node sharing subexpressions) is cheap
+    pub fn derivative(&mut self, id: NodeId, var: Variable) -> NodeId {
+        if let Some(deriv) = self.derivatives.get(&(id, var)) {
+            return *deriv;
+        }
+
+        let deriv = match self.nodes[id.0].clone() {
+            Node::X => self.int((var == Variable::X) as i32),
+            Node::Y => self.int((var == Variable::Y) as i32),
+            Node::Z => self.int((var == Variable::Z) as i32),
+            Node::E | Node::PI | Node::Rational(_) => self.int(0),
+            Node::Binary { operation, terms } => {
+                let (f, g) = terms;
+                let df = self.derivative(f, var);
+                let dg = self.derivative(g, var);
+
+                match operation {
+                    Operation::Add => self.add(df, dg),
+                    Operation::Sub => self.sub(df, dg),
+                    Operation::Mul => {
+                        let left = self.mul(df, g);
+                        let right = self.mul(f, dg);
+                        self.add(left, right)
+                    }
+                    Operation::Div => {
+                        let left = self.mul(df, g);
+                        let right = self.mul(f, dg);
+                        let num = self.sub(left, right);
+                        let two = self.int(2);
+                        let den = self.pow(g, two);
+                        self.div(num, den)
+                    }
+                    Operation::Pow => {
+                        if let Some(exp) = self.rational(g).cloned() {
+                            // (f^r)' = rf^(r-1)f'
+                            let coefficient = self.intern(Node::Rational(exp.clone()));
+                            let exp = self.intern(Node::Rational(exp - 1));
+                            let pow = self.pow(f, exp);
+                            let pow = self.mul(coefficient, pow);
+                            self.mul(pow, df)
+                        } else {
+                            // (f^g)' = f^g(g'ln(f)+gf'/f)
+                            let ln = self.special(FunctionType::Ln, f);
+                            let left = self.mul(dg, ln);
+                            let right = self.mul(g, df);
+                            let right = self.div(right, f);
+                            let sum = self.add(left, right);
+                            self.mul(id, sum)
+                        }
+                    }
+                    Operation::Comp => panic!("Something went wrong"),
+                }
+            }
+            Node::Special { kind, argument } => {
+                let darg = self.derivative(argument, var);
+                let outer = self.special_derivative(&kind, argument);
+                self.mul(darg, outer)
+            }
+        };
+
+        self.derivatives.insert((id, var), deriv);
+        deriv
+    }
+
+    /// Derivative of the special function with respect to its argument
+    fn special_derivative(&mut self, kind: &FunctionType, arg: NodeId) -> NodeId {
+        let one = self.int(1);
+        let two = self.int(2);
+        let minus_one = self.int(-1);
+        let half = self.intern(Node::Rational(Rational::new(1, 2)));
+        let square = self.pow(arg, two);
+
+        match kind {
+            FunctionType::Ln => self.div(one, arg),
+            FunctionType::Sin => self.special(FunctionType::Cos, arg),
+            FunctionType::Cos => {
+                let sin = self.special(FunctionType::Sin, arg);
+                self.mul(minus_one, sin)
+            }
+            FunctionType::Tan => {
+                let sec = self.special(FunctionType::Sec, arg);
+                self.pow(sec, two)
+            }
+            FunctionType::Cot => {
+                let csc = self.special(FunctionType::Csc, arg);
+                let csc = self.pow(csc, two);
+                self.mul(minus_one, csc)
+            }
+            FunctionType::Sec => {
+                let sec = self.special(FunctionType::Sec, arg);
+                let tan = self.special(FunctionType::Tan, arg);
+                self.mul(sec, tan)
+            }
+            FunctionType::Csc => {
+                let csc = self.special(FunctionType::Csc, arg);
+                let cot = self.special(FunctionType::Cot, arg);
+                let prod = self.mul(csc, cot);
+                self.mul(minus_one, prod)
+            }
+            FunctionType::ASin | FunctionType::ACos => {
+                let diff = self.sub(one, square);
+                let sqrt = self.pow(diff, half);
+                let sign = if let FunctionType::ASin = kind {
+                    one
+                } else {
+                    minus_one
+                };
+                self.div(sign, sqrt)
+            }
+            FunctionType::ATan => {
+                let sum = self.add(one, square);
+                self.div(one, sum)
+            }
+            FunctionType::Sinh => self.special(FunctionType::Cosh, arg),
+            FunctionType::Cosh => self.special(FunctionType::Sinh, arg),
+            FunctionType::Tanh => {
+                let sech = self.special(FunctionType::Sech, arg);
+                self.pow(sech, two)
+            }
+            FunctionType::Coth => {
+                let csch = self.special(FunctionType::Csch, arg);
+                let csch = self.pow(csch, two);
+                self.mul(minus_one, csch)
+            }
+            FunctionType::Sech => {
+                let sech = self.special(FunctionType::Sech, arg);
+                let tanh = self.special(FunctionType::Tanh, arg);
+                let prod = self.mul(sech, tanh);
+                self.mul(minus_one, prod)
+            }
+            FunctionType::Csch => {
+                let csch = self.special(FunctionType::Csch, arg);
+                let coth = self.special(FunctionType::Coth, arg);
+                let prod = self.mul(csch, coth);
+                self.mul(minus_one, prod)
+            }
+            FunctionType::ASinh => {
+                let sum = self.add(square, one);
+                let sqrt = self.pow(sum, half);
+                self.div(one, sqrt)
+            }
+            FunctionType::ACosh => {
+                let diff = self.sub(square, one);
+                let sqrt = self.pow(diff, half);
+                self.div(one, sqrt)
+            }
+            FunctionType::ATanh => {
+                let diff = self.sub(one, square);
+                self.div(one, diff)
+            }
+            FunctionType::Abs => {
+                let abs = self.special(FunctionType::Abs, arg);
+                self.div(arg, abs)
+            }
+        }
+    }
+
+    /// Computes the hessian matrix of the node with respect to the given variables
+    pub fn hessian(&mut self, id: NodeId, vars: &[Variable]) -> Matrix<NodeId> {
+        let mut mat = Vec::with_capacity(vars.len() * vars.len());
+        for first in vars {
+            let deriv = self.derivative(id, *first);
+            for second in vars {
+                mat.push(self.derivative(deriv, *second));
+            }
+        }
+
+        Matrix::new(mat, vars.len(), vars.len())
+    }
+
+    pub(crate) fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0]
+    }
+
+    /// Marks the nodes the given roots depend on
+    pub(crate) fn reachable(&self, roots: &[NodeId]) -> Vec<bool> {
+        let mut needed = vec![false; self.nodes.len()];
+        let mut stack: Vec<NodeId> = roots.to_vec();
+        while let Some(id) = stack.pop() {
+            if needed[id.0] {
+                continue;
+            }
+            needed[id.0] = true;
+            match &self.nodes[id.0] {
+                Node::Binary { terms, .. } => stack.extend([terms.0, terms.1]),
+                Node::Special { argument, .. } => stack.push(*argument),
+                _ => {}
+            }
+        }
+        needed
+    }
+
+    /// Evaluates the given nodes at (x,y,z), computing every node they depend on only once
+    pub fn eval<T: Scalar>(&self, roots: &[NodeId], inputs: &[T; 3]) -> Vec<T> {
+        let needed = self.reachable(roots);
+
+        let mut values: Vec<Option<T>> = vec![None; self.nodes.len()];
+        for (idx, node) in self.nodes.iter().enumerate() {
+            if !needed[idx] {
+                continue;
+            }
+            let value = |id: &NodeId| values[id.0].clone().expect("Children come first");
+
+            values[idx] = Some(match node {
+                Node::X => inputs[0].clone(),
+                Node::Y => inputs[1].clone(),
+                Node::Z => inputs[2].clone(),
+                Node::E => T::e(),
+                Node::PI => T::pi(),
+                Node::Rational(val) => T::from_rational(val),
+                Node::Binary { operation, terms } => {
+                    eval_ops(operation, value(&terms.0), value(&terms.1))
+                }
+                Node::Special { kind, argument } => eval_trascendental(kind, value(argument)),
+            });
+        }
+
+        roots
+            .iter()
+            .map(|id| values[id.0].clone().expect("Roots are evaluated"))
+            .collect()
+    }
+}
+
+#[test]
+fn test_dag() {
+    use std::str::FromStr;
+
+    fn size(func: &Function) -> usize {
+        match func {
+            Function::Binary { terms, .. } => 1 + size(&terms.0) + size(&terms.1),
+            Function::Special { argument, .. } => 1 + size(argument),
+            _ => 1,
+        }
+    }
+
+    let func = F3D::from_str("e^(xyz)sin(x^2+y^2)/(1+cos(x^2+y^2))+ln(z)^3").unwrap();
+    let hessian = func.hessian();
+
+    let mut dag = Dag::new();
+    let id = dag.insert_f3d(&func);
+    assert_eq!(dag.to_f3d(id), func);
+
+    let vars = [Variable::X, Variable::Y, Variable::Z];
+    let dag_hessian = dag.hessian(id, &vars);
+
+    let tree_size: usize = hessian.as_slice().iter().map(|func| size(&func.0)).sum();
+    assert!(dag.len() * 5 < tree_size);
+
+    let expected = hessian.eval(0.3, 0.7, 1.2);
+    let values = dag.eval(dag_hessian.as_slice(), &[0.3, 0.7, 1.2]);
+    for (i, value) in values.iter().enumerate() {
+        let expected = expected.get(i / 3 + 1, i % 3 + 1);
+        assert!((value - expected).abs() < 1e-9 * expected.abs().max(1.));
+    }
+
+    // x*y and y*x are the same node
+    let first = dag.insert_f2d(&F2D::from_str("xy").unwrap());
+    let second = dag.insert_f2d(&F2D::from_str("yx").unwrap());
+    assert_eq!(first, second);
+    assert_eq!(dag.to_f3d(first), F3D::from_str("xy").unwrap());
+    let first = dag.insert_f2d(&F2D::from_str("sin(x)+e^y").unwrap());
+    let second = dag.insert_f2d(&F2D::from_str("e^y+sin(x)").unwrap());
+    assert_eq!(first, second);
+    let third = dag.insert_f2d(&F2D::from_str("sin(x)-e^y").unwrap());
+    assert_ne!(first, third);
+}
@@ -0,0 +1,203 @@
+use crate::{Function, Variable, F1D, F2D, F3D};
+use std::collections::HashMap;
+
+/// Partial derivatives of a function indexed by how many times each variable is differentiated,
+/// every one computed from a cached lower order
+struct Partials {
+    cache: HashMap<[u32; 3], Function>,
+}
+
+impl Partials {
+    fn new(func: &Function) -> Self {
+        Partials {
+            cache: HashMap::from([([0, 0, 0], func.clone())]),
+        }
+    }
+
+    fn get(&mut self, orders: [u32; 3]) -> Function {
+        if let Some(func) = self.cache.get(&orders) {
+            return func.clone();
+        }
+
+        // Mixed partials are assumed to commute, so any lower order works
+        let var = orders
+            .iter()
+            .position(|order| *order > 0)
+            .expect("the zeroth order is always cached");
+        let mut lower = orders;
+        lower[var] -= 1;
+
+        let func = self.get(lower).derivative_by(var).simplify();
+        self.cache.insert(orders, func.clone());
+        func
+    }
+}
+
+/// Adds up the orders of each variable
+fn total_orders(orders: &[(Variable, u32)]) -> [u32; 3] {
+    let mut total = [0; 3];
+    for (var, order) in orders {
+        let index = match var {
+            Variable::X => 0,
+            Variable::Y => 1,
+            Variable::Z => 2,
+        };
+        total[index] += order;
+    }
+    total
+}
+
+/// Every split of `order` between x, y and (if there are three variables) z, higher orders of x
+/// first
+fn multi_indices(order: u32, three_vars: bool) -> Vec<[u32; 3]> {
+    let mut indices = Vec::new();
+    for x in (0..=order).rev() {
+        if three_vars {
+            for y in (0..=order - x).rev() {
+                indices.push([x, y, order - x - y]);
+            }
+        } else {
+            indices.push([x, order - x, 0]);
+        }
+    }
+    indices
+}
+
+impl F1D {
+    /// Computes the n-th derivative, simplifying after every step
+    /// ```
+    /// use ruut_functions::F1D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("x^4+sin(x)").unwrap();
+    ///
+    /// assert_eq!(func.nth_derivative(3).to_string(), "24x-cos(x)");
+    /// assert_eq!(func.nth_derivative(0), func);
+    /// ```
+    pub fn nth_derivative(&self, n: u32) -> Self {
+        F1D(Partials::new(&self.0).get([n, 0, 0]))
+    }
+}
+
+impl F2D {
+    /// Computes a partial derivative of any order, e.g. [(X, 2), (Y, 1)] is ∂³f/∂x²∂y. Mixed
+    /// partials are taken in any order, as for functions with continuous derivatives
+    /// ```
+    /// use ruut_functions::{F2D, Variable};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("x^3y^2").unwrap();
+    ///
+    /// assert_eq!(
+    ///     func.partial(&[(Variable::X, 2), (Variable::Y, 1)]),
+    ///     F2D::from_str("12xy").unwrap().simplify()
+    /// );
+    /// ```
+    pub fn partial(&self, orders: &[(Variable, u32)]) -> Self {
+        F2D(Partials::new(&self.0).get(total_orders(orders)))
+    }
+
+    /// Computes all the partial derivatives of the given order, each from the cached lower ones.
+    /// The orders of x and y come with each derivative, higher orders of x first
+    /// ```
+    /// use ruut_functions::F2D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("x^2y").unwrap();
+    /// let partials = func.partials(2);
+    ///
+    /// assert_eq!(partials[0], ([2, 0], F2D::from_str("2y").unwrap().simplify()));
+    /// assert_eq!(partials[2], ([0, 2], F2D::from_str("0").unwrap()));
+    /// ```
+    pub fn partials(&self, order: u32) -> Vec<([u32; 2], Self)> {
+        let mut partials = Partials::new(&self.0);
+        multi_indices(order, false)
+            .into_iter()
+            .map(|[x, y, z]| ([x, y], F2D(partials.get([x, y, z]))))
+            .collect()
+    }
+}
+
+impl F3D {
+    /// Computes a partial derivative of any order, e.g. [(X, 1), (Z, 2)] is ∂³f/∂x∂z². Mixed
+    /// partials are taken in any order, as for functions with continuous derivatives
+    /// ```
+    /// use ruut_functions::{F3D, Variable};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("e^(xy)z^3").unwrap();
+    ///
+    /// assert_eq!(
+    ///     func.partial(&[(Variable::Z, 3), (Variable::Y, 1)]),
+    ///     F3D::from_str("6xe^(xy)").unwrap().simplify()
+    /// );
+    /// ```
+    pub fn partial(&self, orders: &[(Variable, u32)]) -> Self {
+        F3D(Partials::new(&self.0).get(total_orders(orders)))
+    }
+
+    /// Computes all the partial derivatives of the given order, each from the cached lower ones.
+    /// The orders of x, y and z come with each derivative, higher orders of x first
+    /// ```
+    /// use ruut_functions::F3D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("xyz").unwrap();
+    /// let partials = func.partials(3);
+    ///
+    /// assert_eq!(partials.len(), 10);
+    /// assert!(partials.contains(&([1, 1, 1], F3D::from_str("1").unwrap())));
+    /// ```
+    pub fn partials(&self, order: u32) -> Vec<([u32; 3], Self)> {
+        let mut partials = Partials::new(&self.0);
+        multi_indices(order, true)
+            .into_iter()
+            .map(|orders| (orders, F3D(partials.get(orders))))
+            .collect()
+    }
+}
+
+#[test]
+fn test_derivatives() {
+    use std::str::FromStr;
+
+    let cases = [
+        ("sin(x)", 4, "sin(x)"),
+        ("e^(2x)", 3, "8e^(2x)"),
+        ("x^5", 5, "120"),
+        ("x^5", 6, "0"),
+        ("ln(x)", 3, "2/x^3"),
+    ];
+    for (func, n, expected) in cases {
+        let func = F1D::from_str(func).unwrap();
+        assert_eq!(
+            func.nth_derivative(n),
+            F1D::from_str(expected).unwrap().simplify(),
+            "{func}"
+        );
+    }
+
+    // Same as differentiating step by step, in any order
+    let func = F3D::from_str("x^2sin(yz)").unwrap();
+    let stepwise = func.derivative().y.derivative().x.derivative().z;
+    let partial = func.partial(&[(Variable::Z, 1), (Variable::X, 1), (Variable::Y, 1)]);
+    assert!((partial.eval(0.3, 1.2, -0.7) - stepwise.eval(0.3, 1.2, -0.7)).abs() < 1e-12);
+
+    // Fourth order mixed partials of a F2D
+    let func = F2D::from_str("x^3y^2+e^(x+y)").unwrap();
+    let partials = func.partials(4);
+    assert_eq!(partials.len(), 5);
+    for (orders, partial) in partials {
+        let expected = match orders {
+            [3, 1] => "12y+e^(x+y)",
+            [2, 2] => "12x+e^(x+y)",
+            _ => "e^(x+y)",
+        };
+        let expected = F2D::from_str(expected).unwrap();
+        assert!((partial.eval(0.5, -1.) - expected.eval(0.5, -1.)).abs() < 1e-12);
+    }
+    assert_eq!(
+        func.partial(&[(Variable::Z, 1)]),
+        F2D::from_str("0").unwrap()
+    );
+}
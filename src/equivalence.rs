@@ -0,0 +1,239 @@
+use crate::{Function, F1D, F2D, F3D};
+
+#[derive(Debug, Clone, PartialEq)]
+/// Settings of the numeric part of `is_equivalent`
+pub struct EquivalenceOptions {
+    /// Probability the functions are equivalent when all the sampled points agree. Every agreeing
+    /// point is assumed to halve the chance of a false positive, so 1 - 1e-9 takes 30 points.
+    /// Accepted values are in [0, 1), anything closer to 1 than f64::EPSILON is treated as
+    /// 1 - f64::EPSILON, which takes 52 points. NaN and infinities use the default
+    pub confidence: f64,
+    /// Relative tolerance when comparing values
+    pub tolerance: f64,
+    /// Points are sampled in [-range, range] for each variable
+    pub range: f64,
+    /// Seed of the random number generator, the same seed gives the same points
+    pub seed: u64,
+}
+
+impl Default for EquivalenceOptions {
+    fn default() -> Self {
+        EquivalenceOptions {
+            confidence: 1. - 1e-9,
+            tolerance: 1e-9,
+            range: 4.,
+            seed: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Outcome of an equivalence check, N is the number of variables
+pub enum Equivalence<const N: usize> {
+    /// The canonical forms are the same
+    Proven,
+    /// The functions agree at every sampled point
+    Probable,
+    /// The functions differ at the given point
+    Different([f64; N]),
+    /// There weren't enough points where both functions are defined
+    Inconclusive,
+}
+
+impl<const N: usize> Equivalence<N> {
+    /// Checks if the functions were found equivalent, either proven or probable
+    pub fn holds(&self) -> bool {
+        matches!(self, Self::Proven | Self::Probable)
+    }
+}
+
+/// xorshift64*, deterministic and good enough to pick points
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> f64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        let bits = self.0.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 11;
+        // Uniform in [0, 1)
+        bits as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn check<const N: usize>(
+    first: &Function,
+    second: &Function,
+    options: &EquivalenceOptions,
+) -> Equivalence<N> {
+    if first.simplify() == second.simplify() || first.expand() == second.expand() {
+        return Equivalence::Proven;
+    }
+
+    let confidence = match options.confidence.is_finite() {
+        true => options.confidence.clamp(0., 1. - f64::EPSILON),
+        false => EquivalenceOptions::default().confidence,
+    };
+    let needed = (-(1. - confidence).log2()).ceil().max(1.) as usize;
+    // Zero would be a fixed point of the generator
+    let mut rng = Rng(options.seed.max(1));
+    let mut agreeing = 0;
+
+    for _ in 0..needed * 100 {
+        let mut point = [0.; N];
+        for coord in point.iter_mut() {
+            *coord = (2. * rng.next() - 1.) * options.range;
+        }
+        let mut inputs = [0.; 3];
+        inputs[..N].copy_from_slice(&point);
+
+        let (value, other) = (first.evaluate(&inputs), second.evaluate(&inputs));
+        // Points outside of either domain say nothing
+        if !value.is_finite() || !other.is_finite() {
+            continue;
+        }
+        let scale = value.abs().max(other.abs()).max(1.);
+        if (value - other).abs() > options.tolerance * scale {
+            return Equivalence::Different(point);
+        }
+
+        agreeing += 1;
+        if agreeing == needed {
+            return Equivalence::Probable;
+        }
+    }
+
+    Equivalence::Inconclusive
+}
+
+impl F1D {
+    /// Checks if two functions are mathematically equal, first comparing canonical forms and then
+    /// evaluating them at random points
+    /// ```
+    /// use ruut_functions::{F1D, equivalence::{Equivalence, EquivalenceOptions}};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("sin(x)^2+cos(x)^2").unwrap();
+    /// let one = F1D::from_str("1").unwrap();
+    /// let options = EquivalenceOptions::default();
+    ///
+    /// assert_eq!(func.is_equivalent(&one, &options), Equivalence::Probable);
+    ///
+    /// let func = F1D::from_str("(x+1)^2").unwrap();
+    /// let wrong = F1D::from_str("x^2+1").unwrap();
+    /// assert!(matches!(func.is_equivalent(&wrong, &options), Equivalence::Different(_)));
+    /// ```
+    pub fn is_equivalent(&self, other: &F1D, options: &EquivalenceOptions) -> Equivalence<1> {
+        check(&self.0, &other.0, options)
+    }
+}
+
+impl F2D {
+    /// Checks if two functions are mathematically equal, first comparing canonical forms and then
+    /// evaluating them at random points
+    /// ```
+    /// use ruut_functions::{F2D, equivalence::{Equivalence, EquivalenceOptions}};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("x+y").unwrap();
+    /// let other = F2D::from_str("y+x").unwrap();
+    ///
+    /// assert_eq!(func.is_equivalent(&other, &EquivalenceOptions::default()), Equivalence::Proven);
+    /// ```
+    pub fn is_equivalent(&self, other: &F2D, options: &EquivalenceOptions) -> Equivalence<2> {
+        check(&self.0, &other.0, options)
+    }
+}
+
+impl F3D {
+    /// Checks if two functions are mathematically equal, first comparing canonical forms and then
+    /// evaluating them at random points
+    /// ```
+    /// use ruut_functions::{F3D, equivalence::EquivalenceOptions};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("e^(x+y)z").unwrap();
+    /// let other = F3D::from_str("ze^xe^y").unwrap();
+    ///
+    /// assert!(func.is_equivalent(&other, &EquivalenceOptions::default()).holds());
+    /// ```
+    pub fn is_equivalent(&self, other: &F3D, options: &EquivalenceOptions) -> Equivalence<3> {
+        check(&self.0, &other.0, options)
+    }
+}
+
+#[test]
+fn test_equivalence() {
+    use std::str::FromStr;
+
+    let options = EquivalenceOptions::default();
+    let cases = [
+        ("x(x+1)", "x^2+x", true),
+        ("tan(x)", "sin(x)/cos(x)", true),
+        ("ln(x^2)", "2ln(abs(x))", true),
+        ("cosh(x)^2-sinh(x)^2", "1", true),
+        ("(x^2-1)/(x-1)", "x+1", true),
+        ("x^(1/2)", "abs(x)^(1/2)", true),
+        ("sin(2x)", "2sin(x)", false),
+        ("x+1/1000", "x", false),
+    ];
+    for (func, other, equivalent) in cases {
+        let (func, other) = (F1D::from_str(func).unwrap(), F1D::from_str(other).unwrap());
+        let result = func.is_equivalent(&other, &options);
+        assert_eq!(result.holds(), equivalent, "{func} {other}");
+        if let Equivalence::Different([x]) = result {
+            assert!((func.eval(x) - other.eval(x)).abs() > options.tolerance);
+        }
+    }
+
+    // Never defined at the same points
+    let func = F1D::from_str("ln(x)").unwrap();
+    let other = F1D::from_str("ln(-x)").unwrap();
+    assert_eq!(
+        func.is_equivalent(&other, &options),
+        Equivalence::Inconclusive
+    );
+
+    // Same seed, same counterexample
+    let func = F2D::from_str("xy").unwrap();
+    let other = F2D::from_str("x+y").unwrap();
+    assert_eq!(
+        func.is_equivalent(&other, &options),
+        func.is_equivalent(&other, &options)
+    );
+
+    let loose = EquivalenceOptions {
+        tolerance: 1e-2,
+        ..EquivalenceOptions::default()
+    };
+    let func = F3D::from_str("x+y+z+1/1000").unwrap();
+    let other = F3D::from_str("z+y+x").unwrap();
+    assert!(func.is_equivalent(&other, &loose).holds());
+    assert!(!func.is_equivalent(&other, &options).holds());
+
+    // Out of range confidences are clamped
+    let func = F1D::from_str("sin(x)^2+cos(x)^2").unwrap();
+    let one = F1D::from_str("1").unwrap();
+    for confidence in [1., 2., -1.] {
+        let options = EquivalenceOptions {
+            confidence,
+            ..EquivalenceOptions::default()
+        };
+        assert_eq!(func.is_equivalent(&one, &options), Equivalence::Probable);
+    }
+
+    // Non finite ones fall back to the default, a single point wouldn't find the difference
+    let func = F1D::from_str("x+abs(x+3)-(x+3)").unwrap();
+    let other = F1D::from_str("x").unwrap();
+    let with = |confidence| EquivalenceOptions {
+        confidence,
+        ..EquivalenceOptions::default()
+    };
+    assert_eq!(func.is_equivalent(&other, &with(0.)), Equivalence::Probable);
+    for confidence in [f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            func.is_equivalent(&other, &with(confidence)),
+            Equivalence::Different(_)
+        ));
+    }
+}
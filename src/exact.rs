@@ -0,0 +1,188 @@
+use crate::algebra::rational::Rational;
+use crate::{Function, FunctionType, Operation, F1D, F2D, F3D};
+use std::error::Error;
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq)]
+/// Errors that can occur while evaluating exactly
+pub enum ExactEvalError {
+    /// The given node doesn't have a rational value
+    NotRational(String),
+    /// A denominator evaluated to zero
+    DivisionByZero,
+    /// The result doesn't fit in a Rational
+    Overflow,
+}
+
+impl Display for ExactEvalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotRational(node) => write!(f, "{node} can't be evaluated exactly"),
+            Self::DivisionByZero => write!(f, "Division by zero"),
+            Self::Overflow => write!(f, "Overflow while evaluating exactly"),
+        }
+    }
+}
+impl Error for ExactEvalError {}
+
+impl Function {
+    fn evaluate_exact(&self, inputs: &[Rational; 3]) -> Result<Rational, ExactEvalError> {
+        let not_rational = || ExactEvalError::NotRational(self.to_string());
+
+        match self {
+            Self::X => Ok(inputs[0].clone()),
+            Self::Y => Ok(inputs[1].clone()),
+            Self::Z => Ok(inputs[2].clone()),
+            Self::Rational(val) => Ok(val.clone()),
+            Self::E | Self::PI => Err(not_rational()),
+            Self::Binary { operation, terms } => {
+                let left = terms.0.evaluate_exact(inputs)?;
+                let right = terms.1.evaluate_exact(inputs)?;
+
+                if right == 0 {
+                    if let Operation::Div = operation {
+                        return Err(ExactEvalError::DivisionByZero);
+                    }
+                }
+                if left == 0 && right.num() < 0 {
+                    if let Operation::Pow = operation {
+                        return Err(ExactEvalError::DivisionByZero);
+                    }
+                }
+
+                match operation {
+                    Operation::Add => left.checked_add(right),
+                    Operation::Sub => left.checked_sub(right),
+                    Operation::Mul => left.checked_mul(right),
+                    Operation::Div => left.checked_div(right),
+                    Operation::Pow => {
+                        let root = left
+                            .checked_root(right.den() as u32)
+                            .ok_or_else(not_rational)?;
+                        root.checked_pow(right.num())
+                    }
+                    Operation::Comp => panic!("Something went wrong"),
+                }
+                .ok_or(ExactEvalError::Overflow)
+            }
+            Self::Special { kind, argument } => match kind {
+                FunctionType::Abs => {
+                    let arg = argument.evaluate_exact(inputs)?;
+                    let num = arg.num().checked_abs().ok_or(ExactEvalError::Overflow)?;
+                    Ok(Rational::new(num, arg.den()))
+                }
+                _ => Err(not_rational()),
+            },
+        }
+    }
+}
+
+impl F1D {
+    /// Evaluate F1D exactly at a rational x, possible only when the function is built with
+    /// +, -, *, / and rational powers
+    /// ```
+    /// use ruut_functions::{F1D, algebra::rational::Rational, exact::ExactEvalError};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("x/3+x^2/4").unwrap();
+    /// assert_eq!(func.eval_exact(Rational::new_from_int(1)), Ok(Rational::new(7, 12)));
+    ///
+    /// let func = F1D::from_str("x+sin(x)").unwrap();
+    /// assert_eq!(
+    ///     func.eval_exact(Rational::new_from_int(1)),
+    ///     Err(ExactEvalError::NotRational("sin(x)".to_string()))
+    /// );
+    /// ```
+    pub fn eval_exact(&self, x: Rational) -> Result<Rational, ExactEvalError> {
+        self.0
+            .evaluate_exact(&[x, Rational::zero(), Rational::zero()])
+    }
+}
+impl F2D {
+    /// Evaluate F2D exactly at a rational (x,y), possible only when the function is built with
+    /// +, -, *, / and rational powers
+    /// ```
+    /// use ruut_functions::{F2D, algebra::rational::Rational};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("(x+y)^(1/2)").unwrap();
+    ///
+    /// assert_eq!(
+    ///     func.eval_exact(Rational::new(1, 2), Rational::new(7, 4)),
+    ///     Ok(Rational::new(3, 2))
+    /// );
+    /// ```
+    pub fn eval_exact(&self, x: Rational, y: Rational) -> Result<Rational, ExactEvalError> {
+        self.0.evaluate_exact(&[x, y, Rational::zero()])
+    }
+}
+impl F3D {
+    /// Evaluate F3D exactly at a rational (x,y,z), possible only when the function is built
+    /// with +, -, *, / and rational powers
+    /// ```
+    /// use ruut_functions::{F3D, algebra::rational::Rational, exact::ExactEvalError};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("xy/z").unwrap();
+    ///
+    /// assert_eq!(
+    ///     func.eval_exact(Rational::new_from_int(1), Rational::new(2, 3), Rational::zero()),
+    ///     Err(ExactEvalError::DivisionByZero)
+    /// );
+    /// ```
+    pub fn eval_exact(
+        &self,
+        x: Rational,
+        y: Rational,
+        z: Rational,
+    ) -> Result<Rational, ExactEvalError> {
+        self.0.evaluate_exact(&[x, y, z])
+    }
+}
+
+#[test]
+fn test_exact() {
+    use std::str::FromStr;
+
+    let func = F1D::from_str("(x+1)^3-x^(-2)+abs(x-2)").unwrap();
+    assert_eq!(
+        func.eval_exact(Rational::new(1, 2)),
+        Ok(Rational::new(7, 8))
+    );
+
+    let func = F1D::from_str("x^(2/3)").unwrap();
+    assert_eq!(
+        func.eval_exact(Rational::new(-8, 27)),
+        Ok(Rational::new(4, 9))
+    );
+    assert_eq!(
+        func.eval_exact(Rational::new_from_int(2)),
+        Err(ExactEvalError::NotRational("x^(2/3)".to_string()))
+    );
+
+    let func = F2D::from_str("x^y").unwrap();
+    assert_eq!(
+        func.eval_exact(Rational::new_from_int(2), Rational::new_from_int(-3)),
+        Ok(Rational::new(1, 8))
+    );
+    assert_eq!(
+        func.eval_exact(Rational::new_from_int(1000), Rational::new_from_int(4)),
+        Err(ExactEvalError::Overflow)
+    );
+
+    let func = F3D::from_str("x+ye").unwrap();
+    assert_eq!(
+        func.eval_exact(Rational::zero(), Rational::zero(), Rational::zero()),
+        Err(ExactEvalError::NotRational("e".to_string()))
+    );
+
+    let func = F1D::from_str("abs(x)").unwrap();
+    assert_eq!(
+        func.eval_exact(Rational::new_from_int(i32::MIN)),
+        Err(ExactEvalError::Overflow)
+    );
+    assert_eq!(
+        func.eval_exact(Rational::new_from_int(-i32::MAX)),
+        Ok(Rational::new_from_int(i32::MAX))
+    );
+}
@@ -0,0 +1,383 @@
+use crate::algebra::rational::{gcd, Rational};
+use crate::simplify::{binary, normalize, to_function, Sum, Term};
+use crate::{Function, Operation, F1D, F2D, F3D};
+use std::collections::HashSet;
+
+/// Greatest common divisor of two rationals: gcd of the numerators over lcm of the denominators
+pub(crate) fn rational_gcd(first: &Rational, second: &Rational) -> Option<Rational> {
+    let num = gcd(first.num().unsigned_abs(), second.num().unsigned_abs());
+    let den = (first.den() as u64 / gcd(first.den() as u32, second.den() as u32) as u64)
+        * second.den() as u64;
+    Some(Rational::new(
+        i32::try_from(num).ok()?,
+        i32::try_from(den).ok()?,
+    ))
+}
+
+/// Extracts the common coefficient and the common powers of the terms, leaving them in `sum`
+fn common_factor(sum: &mut Sum) -> Option<Term> {
+    let mut coeff = sum[0].coeff.clone();
+    for term in &sum[1..] {
+        coeff = rational_gcd(&coeff, &term.coeff)?;
+    }
+    if sum[0].coeff.num() < 0 {
+        coeff = Rational::new(-coeff.num(), coeff.den());
+    }
+
+    let mut factors = Vec::new();
+    for (base, exp) in &sum[0].factors {
+        let mut min = match exp {
+            Function::Rational(exp) => exp.clone(),
+            _ => continue,
+        };
+        let in_all = sum[1..].iter().all(|term| {
+            term.factors.iter().any(|(other, exp)| match exp {
+                Function::Rational(exp) if other == base => {
+                    if exp.eval() < min.eval() {
+                        min = exp.clone();
+                    }
+                    true
+                }
+                _ => false,
+            })
+        });
+        if in_all {
+            factors.push((base.clone(), min));
+        }
+    }
+
+    for term in sum.iter_mut() {
+        term.coeff = term.coeff.clone().checked_div(coeff.clone())?;
+        for (base, min) in &factors {
+            let idx = term.factors.iter().position(|(other, _)| other == base)?;
+            let exp = match &term.factors[idx].1 {
+                Function::Rational(exp) => exp.clone().checked_sub(min.clone())?,
+                _ => return None,
+            };
+            if exp == 0 {
+                term.factors.remove(idx);
+            } else {
+                term.factors[idx].1 = Function::Rational(exp);
+            }
+        }
+    }
+
+    Some(Term {
+        coeff,
+        factors: factors
+            .into_iter()
+            .map(|(base, exp)| (base, Function::Rational(exp)))
+            .collect(),
+    })
+}
+
+/// Coefficients (from the constant one) of a univariate polynomial
+fn coefficients(sum: &Sum) -> Option<(Function, Vec<Rational>)> {
+    let mut variable = None;
+    let mut coeffs = Vec::new();
+
+    for term in sum {
+        let degree = match term.factors.as_slice() {
+            [] => 0,
+            [(base @ (Function::X | Function::Y | Function::Z), Function::Rational(exp))]
+                if exp.is_integer() && exp.num() > 0 =>
+            {
+                if *variable.get_or_insert(base) != base {
+                    return None;
+                }
+                exp.num() as usize
+            }
+            _ => return None,
+        };
+
+        if coeffs.len() <= degree {
+            coeffs.resize(degree + 1, Rational::zero());
+        }
+        coeffs[degree] = term.coeff.clone();
+    }
+
+    Some((variable?.clone(), coeffs))
+}
+
+/// Positive divisors of n that fit in an i32, found in pairs (d, n/d) up to the square root
+fn divisors(n: i32) -> Vec<i32> {
+    let n = n.unsigned_abs();
+    let mut result = Vec::new();
+    let mut d = 1;
+    while d * d <= n {
+        if n.is_multiple_of(d) {
+            result.push(d);
+            if d * d != n {
+                result.push(n / d);
+            }
+        }
+        d += 1;
+    }
+    result.sort_unstable();
+    result
+        .into_iter()
+        .filter_map(|d| i32::try_from(d).ok())
+        .collect()
+}
+
+/// Horner evaluation, None on overflow
+fn eval(coeffs: &[Rational], x: &Rational) -> Option<Rational> {
+    let mut result = Rational::zero();
+    for coeff in coeffs.iter().rev() {
+        result = result.checked_mul(x.clone())?.checked_add(coeff.clone())?;
+    }
+    Some(result)
+}
+
+/// Divides by (x - root), the remainder is known to be zero
+fn deflate(coeffs: &[Rational], root: &Rational) -> Option<Vec<Rational>> {
+    let mut quotient = vec![Rational::zero(); coeffs.len() - 1];
+    let mut carry = Rational::zero();
+    for i in (1..coeffs.len()).rev() {
+        carry = carry
+            .checked_mul(root.clone())?
+            .checked_add(coeffs[i].clone())?;
+        quotient[i - 1] = carry.clone();
+    }
+    Some(quotient)
+}
+
+/// Roots with their multiplicity
+type Roots = Vec<(Rational, i32)>;
+
+/// Rational roots of the polynomial with their multiplicity, largest first. The leftover
+/// quotient is returned with them
+pub(crate) fn rational_roots(mut coeffs: Vec<Rational>) -> Option<(Roots, Vec<Rational>)> {
+    // Scale to integer coefficients so that the rational root theorem applies
+    let mut lcm = 1;
+    for coeff in &coeffs {
+        lcm = lcm / gcd(lcm as u32, coeff.den() as u32) as i32 * coeff.den();
+    }
+    let integers: Vec<i32> = coeffs
+        .iter()
+        .map(|coeff| coeff.num().checked_mul(lcm / coeff.den()))
+        .collect::<Option<_>>()?;
+
+    let (constant, leading) = (integers[0], integers[integers.len() - 1]);
+    if constant == 0 {
+        return Some((Vec::new(), coeffs));
+    }
+    let (numerators, denominators) = (divisors(constant), divisors(leading));
+    if numerators.len() * denominators.len() > 10_000 {
+        return Some((Vec::new(), coeffs));
+    }
+
+    let mut seen = HashSet::new();
+    let mut candidates = Vec::new();
+    for p in &numerators {
+        for q in &denominators {
+            let root = Rational::new(*p, *q);
+            if seen.insert(root.clone()) {
+                candidates.push(root);
+                candidates.push(Rational::new(-p, *q));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.eval().total_cmp(&a.eval()));
+
+    let mut roots = Vec::new();
+    for root in candidates {
+        let mut multiplicity = 0;
+        // Candidates too large to evaluate without overflow are skipped
+        while coeffs.len() > 1 && eval(&coeffs, &root).is_some_and(|value| value == 0) {
+            coeffs = deflate(&coeffs, &root)?;
+            multiplicity += 1;
+        }
+        if multiplicity > 0 {
+            roots.push((root, multiplicity));
+        }
+    }
+
+    Some((roots, coeffs))
+}
+
+fn factor(func: &Function) -> Option<Function> {
+    let mut sum = normalize(func, true);
+    if sum.len() < 2 {
+        return None;
+    }
+
+    let mut common = common_factor(&mut sum)?;
+    let mut factors = Vec::new();
+
+    if let Some((variable, coeffs)) = coefficients(&sum) {
+        let (roots, rest) = rational_roots(coeffs)?;
+        // (x - p/q) becomes (qx - p), its q goes into the coefficient
+        for (root, multiplicity) in roots {
+            let q = Function::Rational(Rational::new_from_int(root.den()));
+            let linear = match root.den() {
+                1 => variable.clone(),
+                _ => binary(Operation::Mul, q, variable.clone()),
+            };
+            let p = Function::Rational(Rational::new_from_int(root.num().abs()));
+            let linear = match root.num() {
+                n if n > 0 => binary(Operation::Sub, linear, p),
+                _ => binary(Operation::Add, linear, p),
+            };
+            factors.push(match multiplicity {
+                1 => linear,
+                _ => binary(
+                    Operation::Pow,
+                    linear,
+                    Function::Rational(Rational::new_from_int(multiplicity)),
+                ),
+            });
+            let den = Rational::new_from_int(root.den()).checked_pow(multiplicity)?;
+            common.coeff = common.coeff.checked_div(den)?;
+        }
+
+        // Leftover with integer coefficients
+        let mut lcm = 1;
+        for coeff in &rest {
+            lcm = lcm / gcd(lcm as u32, coeff.den() as u32) as i32 * coeff.den();
+        }
+        common.coeff = common.coeff.checked_div(Rational::new_from_int(lcm))?;
+        sum = rest
+            .into_iter()
+            .enumerate()
+            .filter(|(_, coeff)| *coeff != 0)
+            .map(|(degree, coeff)| {
+                let mut term = Term::constant(coeff.checked_mul(Rational::new_from_int(lcm))?);
+                if degree > 0 {
+                    let exp = Rational::new_from_int(degree as i32);
+                    term.factors
+                        .push((variable.clone(), Function::Rational(exp)));
+                }
+                Some(term)
+            })
+            .rev()
+            .collect::<Option<_>>()?;
+
+        let content = common_factor(&mut sum)?;
+        common.coeff = common.coeff.checked_mul(content.coeff)?;
+    }
+
+    let rest = to_function(&sum);
+    if rest != Function::Rational(Rational::new_from_int(1)) {
+        factors.push(rest);
+    }
+
+    // The denominator of the coefficient goes last: x(x+1)/2
+    let den = common.coeff.den();
+    common.coeff = Rational::new_from_int(common.coeff.num());
+    let mut result = common.to_function();
+    for factor in factors {
+        result = match result {
+            Function::Rational(val) if val == 1 => factor,
+            result => binary(Operation::Mul, result, factor),
+        };
+    }
+    if den != 1 {
+        result = binary(
+            Operation::Div,
+            result,
+            Function::Rational(Rational::new_from_int(den)),
+        );
+    }
+    Some(result)
+}
+
+impl Function {
+    pub(crate) fn factor(&self) -> Self {
+        factor(self).unwrap_or_else(|| self.simplify())
+    }
+}
+
+impl F1D {
+    /// Pulls out common factors and factors polynomials with rational roots
+    /// ```
+    /// use ruut_functions::F1D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("2x^3-2x").unwrap();
+    ///
+    /// assert_eq!(func.factor().to_string(), "2x(x-1)(x+1)");
+    /// ```
+    pub fn factor(&self) -> Self {
+        F1D(self.0.factor())
+    }
+}
+impl F2D {
+    /// Pulls out common factors and factors polynomials with rational roots
+    /// ```
+    /// use ruut_functions::F2D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("x^2y+2xy^2").unwrap();
+    ///
+    /// assert_eq!(func.factor().to_string(), "xy(x+2y)");
+    /// ```
+    pub fn factor(&self) -> Self {
+        F2D(self.0.factor())
+    }
+}
+impl F3D {
+    /// Pulls out common factors and factors polynomials with rational roots
+    /// ```
+    /// use ruut_functions::F3D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("6xz+3yz").unwrap();
+    ///
+    /// assert_eq!(func.factor().to_string(), "3z(2x+y)");
+    /// ```
+    pub fn factor(&self) -> Self {
+        F3D(self.0.factor())
+    }
+}
+
+#[test]
+fn test_factor() {
+    use std::str::FromStr;
+
+    let cases = [
+        ("x^2-4", "(x-2)(x+2)"),
+        ("x^2+2x+1", "(x+1)^2"),
+        ("2x^2-x", "x(2x-1)"),
+        ("x^3/2-x/2", "x(x-1)(x+1)/2"),
+        ("x^2+1", "x^2+1"),
+        ("-x^2+1", "-(x-1)(x+1)"),
+        ("x^3-x^2+x-1", "(x-1)(x^2+1)"),
+        ("sin(x)^2+sin(x)", "sin(x)(sin(x)+1)"),
+    ];
+    for (func, expected) in cases {
+        let func = F1D::from_str(func).unwrap();
+        let factored = func.factor();
+        assert_eq!(factored.to_string(), expected, "{func}");
+        assert_eq!(factored.expand(), func.expand(), "{func}");
+    }
+
+    let cases = [
+        "(x+2)(x-2)",
+        "(x+y)^3",
+        "(2x-3y)^2(x+1)",
+        "(x+1)/(x-1)+x^(1/2)",
+        "sin(x)(x+y)^2",
+        "e^(x+1)-pi/2",
+        "(1/2)^x+(-2)^3y+x^(-1)+abs(x)y",
+    ];
+    for func in cases {
+        let func = F2D::from_str(func).unwrap();
+        for form in [func.expand(), func.factor(), func.simplify()] {
+            // Display round trip
+            let parsed = F2D::from_str(&form.to_string()).unwrap();
+            assert_eq!(parsed.simplify(), form.simplify(), "{form}");
+            let (value, expected) = (form.eval(0.7, 1.9), func.eval(0.7, 1.9));
+            assert!(
+                (value - expected).abs() < 1e-9 * expected.abs().max(1.),
+                "{form}"
+            );
+        }
+    }
+
+    // Large constant terms, divisors are only searched up to the square root
+    let func = F1D::from_str("x^2-1000000007").unwrap();
+    assert_eq!(func.factor(), func.simplify());
+    let func = F1D::from_str("x^2-2147395600").unwrap();
+    assert_eq!(func.factor().to_string(), "(x-46340)(x+46340)");
+}
@@ -0,0 +1,140 @@
+use crate::{Function, Matrix, Vec2, F2D, F3D};
+
+/// -F_var/F_dependent, the derivative of the dependent variable defined by F = 0
+fn implicit(func: &Function, var: (i32, i32, i32), dependent: (i32, i32, i32)) -> Function {
+    let (x, y, z) = var;
+    let (dx, dy, dz) = dependent;
+    (-1 * func.derivative(x, y, z) / func.derivative(dx, dy, dz)).simplify()
+}
+
+/// Total derivative by var of a function of the variables and of the dependent one, whose
+/// derivative by var is slope
+fn total(
+    func: &Function,
+    var: (i32, i32, i32),
+    dependent: (i32, i32, i32),
+    slope: &Function,
+) -> Function {
+    let (x, y, z) = var;
+    let (dx, dy, dz) = dependent;
+    (func.derivative(x, y, z) + func.derivative(dx, dy, dz) * slope.clone()).simplify()
+}
+
+impl F2D {
+    /// Computes dy/dx = -F_x/F_y for the curve F(x, y) = 0, defined where F_y isn't zero
+    /// ```
+    /// use ruut_functions::F2D;
+    /// use std::str::FromStr;
+    ///
+    /// let circle = F2D::from_str("x^2+y^2-25").unwrap();
+    ///
+    /// assert_eq!(circle.implicit_derivative().to_string(), "-x/y");
+    /// assert_eq!(circle.implicit_derivative().eval(3., 4.), -0.75);
+    /// ```
+    pub fn implicit_derivative(&self) -> Self {
+        F2D(implicit(&self.0, (1, 0, 0), (0, 1, 0)))
+    }
+
+    /// Computes d²y/dx² for the curve F(x, y) = 0, differentiating dy/dx with y as a function of
+    /// x
+    /// ```
+    /// use ruut_functions::F2D;
+    /// use std::str::FromStr;
+    ///
+    /// let circle = F2D::from_str("x^2+y^2-25").unwrap();
+    ///
+    /// // -(x^2+y^2)/y^3 = -25/y^3 on the circle
+    /// assert_eq!(circle.implicit_second_derivative().eval(3., 4.), -25. / 64.);
+    /// ```
+    pub fn implicit_second_derivative(&self) -> Self {
+        let slope = implicit(&self.0, (1, 0, 0), (0, 1, 0));
+        F2D(total(&slope, (1, 0, 0), (0, 1, 0), &slope))
+    }
+}
+
+impl F3D {
+    /// Computes ∂z/∂x = -F_x/F_z and ∂z/∂y = -F_y/F_z for the surface F(x, y, z) = 0, defined
+    /// where F_z isn't zero
+    /// ```
+    /// use ruut_functions::F3D;
+    /// use std::str::FromStr;
+    ///
+    /// let surface = F3D::from_str("xz+yz^2-3").unwrap();
+    /// let gradient = surface.implicit_derivative();
+    ///
+    /// assert_eq!(gradient.x.eval(1., 2., 1.), -0.2);
+    /// assert_eq!(gradient.y.eval(1., 2., 1.), -0.2);
+    /// ```
+    pub fn implicit_derivative(&self) -> Vec2<Self> {
+        Vec2 {
+            x: F3D(implicit(&self.0, (1, 0, 0), (0, 0, 1))),
+            y: F3D(implicit(&self.0, (0, 1, 0), (0, 0, 1))),
+        }
+    }
+
+    /// Computes the second derivatives of z for the surface F(x, y, z) = 0, as the matrix
+    /// [[z_xx, z_xy], [z_yx, z_yy]]
+    /// ```
+    /// use ruut_functions::F3D;
+    /// use std::str::FromStr;
+    ///
+    /// // z = xy
+    /// let surface = F3D::from_str("z-xy").unwrap();
+    /// let second = surface.implicit_second_derivatives().eval(1., 1., 1.);
+    ///
+    /// assert_eq!(second.as_slice(), [0., 1., 1., 0.]);
+    /// ```
+    pub fn implicit_second_derivatives(&self) -> Matrix<Self> {
+        let (on_x, on_y, on_z) = ((1, 0, 0), (0, 1, 0), (0, 0, 1));
+        let z_x = implicit(&self.0, on_x, on_z);
+        let z_y = implicit(&self.0, on_y, on_z);
+
+        Matrix::new(
+            vec![
+                F3D(total(&z_x, on_x, on_z, &z_x)),
+                F3D(total(&z_x, on_y, on_z, &z_y)),
+                F3D(total(&z_y, on_x, on_z, &z_x)),
+                F3D(total(&z_y, on_y, on_z, &z_y)),
+            ],
+            2,
+            2,
+        )
+    }
+}
+
+#[test]
+fn test_implicit() {
+    use std::str::FromStr;
+
+    // Folium of Descartes, compared with the explicit branch through (3/2, 3/2)
+    let folium = F2D::from_str("x^3+y^3-3xy").unwrap();
+    let slope = folium.implicit_derivative();
+    assert_eq!(slope.eval(1.5, 1.5), -1.);
+    assert!((folium.implicit_second_derivative().eval(1.5, 1.5) + 32. / 3.).abs() < 1e-12);
+
+    // y = e^x written implicitly
+    let curve = F2D::from_str("ln(y)-x").unwrap();
+    assert_eq!(curve.implicit_derivative(), F2D::from_str("y").unwrap());
+    let second = curve.implicit_second_derivative();
+    for x in [-1., 0.5, 2.] {
+        assert!((second.eval(x, f64::exp(x)) - f64::exp(x)).abs() < 1e-12);
+    }
+
+    // Sphere, z = (1 - x^2 - y^2)^(1/2) on the upper half
+    let sphere = F3D::from_str("x^2+y^2+z^2-1").unwrap();
+    let gradient = sphere.implicit_derivative();
+    let second = sphere.implicit_second_derivatives();
+    let (x, y) = (0.3, -0.4);
+    let z = f64::sqrt(1. - x * x - y * y);
+    assert!((gradient.x.eval(x, y, z) + x / z).abs() < 1e-12);
+    assert!((gradient.y.eval(x, y, z) + y / z).abs() < 1e-12);
+    let expected = [
+        -(1. - y * y) / z.powi(3),
+        -x * y / z.powi(3),
+        -x * y / z.powi(3),
+        -(1. - x * x) / z.powi(3),
+    ];
+    for (value, expected) in second.eval(x, y, z).as_slice().iter().zip(expected) {
+        assert!((value - expected).abs() < 1e-12);
+    }
+}
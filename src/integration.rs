@@ -0,0 +1,681 @@
+use crate::algebra::rational::Rational;
+use crate::factor::rational_roots;
+use crate::simplify::{binary, contains_variable, normalize, to_function, Term};
+use crate::{Function, FunctionType, Operation, F1D};
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+/// Errors that can occur while integrating symbolically
+pub enum IntegrationError {
+    /// None of the known rules applies to the given function
+    NoClosedForm(String),
+}
+
+impl Display for IntegrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoClosedForm(func) => write!(f, "No closed form found for {func}"),
+        }
+    }
+}
+impl Error for IntegrationError {}
+
+/// How many times by parts and substitution can call each other
+const MAX_DEPTH: u8 = 4;
+
+/// Coefficients from the constant one, without trailing zeros
+type Poly = Vec<Rational>;
+
+fn rational(num: i32, den: i32) -> Function {
+    Function::Rational(Rational::new(num, den))
+}
+
+fn special(kind: FunctionType, argument: Function) -> Function {
+    Function::Special {
+        kind,
+        argument: Box::new(argument),
+    }
+}
+
+/// ln(|func|)
+fn ln_abs(func: Function) -> Function {
+    special(FunctionType::Ln, special(FunctionType::Abs, func))
+}
+
+fn contains_x(func: &Function) -> bool {
+    match func {
+        Function::X => true,
+        Function::Binary { terms, .. } => contains_x(&terms.0) || contains_x(&terms.1),
+        Function::Special { argument, .. } => contains_x(argument),
+        _ => false,
+    }
+}
+
+/// Slope of a linear function of x
+fn linear(func: &Function) -> Option<Rational> {
+    match func.derivative(1, 0, 0).simplify() {
+        Function::Rational(slope) if slope != 0 => Some(slope),
+        _ => None,
+    }
+}
+
+/// Instantiates a formula in x with the given argument
+fn rule(formula: &str, argument: &Function) -> Function {
+    let F1D(func) = F1D::from_str(formula).expect("Invalid integration rule");
+    func.replace(&Function::X, argument)
+}
+
+fn trim(mut poly: Poly) -> Poly {
+    while poly.last().is_some_and(|coeff| *coeff == 0) {
+        poly.pop();
+    }
+    poly
+}
+
+fn poly_add(first: &Poly, second: &Poly) -> Option<Poly> {
+    let mut result = vec![Rational::zero(); first.len().max(second.len())];
+    for poly in [first, second] {
+        for (i, coeff) in poly.iter().enumerate() {
+            result[i] = result[i].clone().checked_add(coeff.clone())?;
+        }
+    }
+    Some(trim(result))
+}
+
+fn poly_scale(poly: &Poly, factor: &Rational) -> Option<Poly> {
+    let result = poly
+        .iter()
+        .map(|coeff| coeff.clone().checked_mul(factor.clone()))
+        .collect::<Option<_>>()?;
+    Some(trim(result))
+}
+
+fn poly_sub(first: &Poly, second: &Poly) -> Option<Poly> {
+    poly_add(first, &poly_scale(second, &Rational::new_from_int(-1))?)
+}
+
+fn poly_mul(first: &Poly, second: &Poly) -> Option<Poly> {
+    if first.is_empty() || second.is_empty() {
+        return Some(Vec::new());
+    }
+    let mut result = vec![Rational::zero(); first.len() + second.len() - 1];
+    for (i, a) in first.iter().enumerate() {
+        for (j, b) in second.iter().enumerate() {
+            let product = a.clone().checked_mul(b.clone())?;
+            result[i + j] = result[i + j].clone().checked_add(product)?;
+        }
+    }
+    Some(trim(result))
+}
+
+fn poly_pow(poly: &Poly, exp: u32) -> Option<Poly> {
+    let mut result = vec![Rational::new_from_int(1)];
+    for _ in 0..exp {
+        result = poly_mul(&result, poly)?;
+    }
+    Some(result)
+}
+
+/// Quotient and remainder of the division
+fn poly_divmod(num: &Poly, den: &Poly) -> Option<(Poly, Poly)> {
+    let lead = den.last()?;
+    let mut quotient = vec![Rational::zero(); (num.len() + 1).saturating_sub(den.len())];
+    let mut rest = num.clone();
+    while rest.len() >= den.len() {
+        let shift = rest.len() - den.len();
+        let coeff = rest.last()?.clone().checked_div(lead.clone())?;
+        let mut monomial = vec![Rational::zero(); shift];
+        monomial.push(coeff.clone());
+        rest = poly_sub(&rest, &poly_mul(&monomial, den)?)?;
+        quotient[shift] = coeff;
+    }
+    Some((trim(quotient), rest))
+}
+
+fn poly_to_function(poly: &Poly) -> Function {
+    let sum = poly
+        .iter()
+        .enumerate()
+        .filter(|(_, coeff)| **coeff != 0)
+        .map(|(degree, coeff)| {
+            let mut term = Term::constant(coeff.clone());
+            if degree > 0 {
+                let exp = Rational::new_from_int(degree as i32);
+                term.factors.push((Function::X, Function::Rational(exp)));
+            }
+            term
+        })
+        .rev()
+        .collect();
+    to_function(&sum)
+}
+
+/// Numerator and denominator of a rational function of x
+fn as_rational(func: &Function) -> Option<(Poly, Poly)> {
+    let one = || vec![Rational::new_from_int(1)];
+    match func {
+        Function::X => Some((vec![Rational::zero(), Rational::new_from_int(1)], one())),
+        Function::Rational(val) => Some((trim(vec![val.clone()]), one())),
+        Function::Binary { operation, terms } => {
+            let (num, den) = as_rational(&terms.0)?;
+            if let Operation::Pow = operation {
+                let exp = match &*terms.1 {
+                    Function::Rational(exp) if exp.is_integer() && exp.num().abs() <= 64 => {
+                        exp.num()
+                    }
+                    _ => return None,
+                };
+                let (num, den) = match exp {
+                    exp if exp >= 0 => (num, den),
+                    _ => (den, num),
+                };
+                let den = poly_pow(&den, exp.unsigned_abs())?;
+                return (!den.is_empty()).then_some((poly_pow(&num, exp.unsigned_abs())?, den));
+            }
+
+            let (other_num, other_den) = as_rational(&terms.1)?;
+            match operation {
+                Operation::Add | Operation::Sub => {
+                    let first = poly_mul(&num, &other_den)?;
+                    let second = poly_mul(&other_num, &den)?;
+                    let num = match operation {
+                        Operation::Add => poly_add(&first, &second)?,
+                        _ => poly_sub(&first, &second)?,
+                    };
+                    Some((num, poly_mul(&den, &other_den)?))
+                }
+                Operation::Mul => Some((poly_mul(&num, &other_num)?, poly_mul(&den, &other_den)?)),
+                Operation::Div if !other_num.is_empty() => {
+                    Some((poly_mul(&num, &other_den)?, poly_mul(&den, &other_num)?))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Solves the linear system given by its augmented matrix
+fn solve(mut rows: Vec<Vec<Rational>>) -> Option<Vec<Rational>> {
+    let n = rows.len();
+    for col in 0..n {
+        let pivot = (col..n).find(|row| rows[*row][col] != 0)?;
+        rows.swap(col, pivot);
+        for row in 0..n {
+            if row == col || rows[row][col] == 0 {
+                continue;
+            }
+            let factor = rows[row][col].clone().checked_div(rows[col][col].clone())?;
+            let pivot = rows[col].clone();
+            for (value, pivot) in rows[row][col..].iter_mut().zip(&pivot[col..]) {
+                let delta = factor.clone().checked_mul(pivot.clone())?;
+                *value = value.clone().checked_sub(delta)?;
+            }
+        }
+    }
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| row[n].clone().checked_div(row[i].clone()))
+        .collect()
+}
+
+/// Partial fraction of the decomposition
+enum Fraction {
+    /// c / (x - root)^power
+    Linear(Rational, u32),
+    /// (bx + c) / (x^2 + px + s), the first is the b part
+    Quadratic(bool),
+}
+
+fn integrate_polynomial(poly: &Poly) -> Option<Function> {
+    let mut result = vec![Rational::zero()];
+    for (degree, coeff) in poly.iter().enumerate() {
+        let den = Rational::new_from_int(degree as i32 + 1);
+        result.push(coeff.clone().checked_div(den)?);
+    }
+    Some(poly_to_function(&trim(result)))
+}
+
+/// Polynomial division followed by partial fractions. The denominator has to split into rational
+/// roots and at most one quadratic
+fn integrate_rational(num: &Poly, den: &Poly) -> Option<Function> {
+    let lead = Rational::new_from_int(1).checked_div(den.last()?.clone())?;
+    let (num, den) = (poly_scale(num, &lead)?, poly_scale(den, &lead)?);
+    let (quotient, rest) = poly_divmod(&num, &den)?;
+    let mut result = integrate_polynomial(&quotient)?;
+    if rest.is_empty() {
+        return Some(result);
+    }
+
+    let zeros = den.iter().take_while(|coeff| **coeff == 0).count();
+    let (mut roots, quadratic) = rational_roots(den[zeros..].to_vec())?;
+    if zeros > 0 {
+        roots.push((Rational::zero(), zeros as i32));
+    }
+    let quadratic = match quadratic.len() {
+        1 => None,
+        3 => Some(quadratic),
+        _ => return None,
+    };
+
+    let mut fractions = Vec::new();
+    for (root, multiplicity) in &roots {
+        let factor = vec![
+            Rational::zero().checked_sub(root.clone())?,
+            Rational::new_from_int(1),
+        ];
+        for power in 1..=*multiplicity as u32 {
+            let (basis, _) = poly_divmod(&den, &poly_pow(&factor, power)?)?;
+            fractions.push((basis, Fraction::Linear(root.clone(), power)));
+        }
+    }
+    if let Some(quadratic) = &quadratic {
+        let (basis, _) = poly_divmod(&den, quadratic)?;
+        let x = vec![Rational::zero(), Rational::new_from_int(1)];
+        fractions.push((poly_mul(&basis, &x)?, Fraction::Quadratic(true)));
+        fractions.push((basis, Fraction::Quadratic(false)));
+    }
+
+    // Matching the coefficients of rest = sum of c_i * den / fraction_i
+    let n = den.len() - 1;
+    let coeff = |poly: &Poly, i: usize| poly.get(i).cloned().unwrap_or_else(Rational::zero);
+    let rows = (0..n)
+        .map(|i| {
+            let mut row: Vec<_> = fractions.iter().map(|(basis, _)| coeff(basis, i)).collect();
+            row.push(coeff(&rest, i));
+            row
+        })
+        .collect();
+    let solution = solve(rows)?;
+
+    let (mut b, mut c) = (Rational::zero(), Rational::zero());
+    for (value, (_, fraction)) in solution.into_iter().zip(&fractions) {
+        match fraction {
+            Fraction::Linear(root, power) => {
+                let linear = poly_to_function(&vec![
+                    Rational::zero().checked_sub(root.clone())?,
+                    Rational::new_from_int(1),
+                ]);
+                let part = match power {
+                    1 => ln_abs(linear),
+                    _ => {
+                        let power = *power as i32;
+                        binary(
+                            Operation::Div,
+                            rational(-1, power - 1),
+                            binary(Operation::Pow, linear, rational(power - 1, 1)),
+                        )
+                    }
+                };
+                result = binary(
+                    Operation::Add,
+                    result,
+                    binary(Operation::Mul, Function::Rational(value), part),
+                );
+            }
+            Fraction::Quadratic(true) => b = value,
+            Fraction::Quadratic(false) => c = value,
+        }
+    }
+
+    if let Some(quadratic) = quadratic {
+        let (s, p) = (quadratic[0].clone(), quadratic[1].clone());
+        let q = poly_to_function(&quadratic);
+        // (bx + c) / q = b/2 * q' / q + (c - bp/2) / q
+        let half = Rational::new(1, 2);
+        let log = binary(
+            Operation::Mul,
+            Function::Rational(b.clone().checked_mul(half.clone())?),
+            ln_abs(q),
+        );
+        let rest = c.checked_sub(b.checked_mul(p.clone())?.checked_mul(half)?)?;
+        // 4s - p^2
+        let disc = Rational::new_from_int(4)
+            .checked_mul(s)?
+            .checked_sub(p.clone().checked_mul(p.clone())?)?;
+        let inv_sqrt =
+            |val: Rational| binary(Operation::Pow, Function::Rational(val), rational(-1, 2));
+        let part = if disc.num() > 0 {
+            let linear = poly_to_function(&vec![p, Rational::new_from_int(2)]);
+            // 2/sqrt(disc) atan((2x + p)/sqrt(disc))
+            binary(
+                Operation::Mul,
+                binary(Operation::Mul, rational(2, 1), inv_sqrt(disc.clone())),
+                special(
+                    FunctionType::ATan,
+                    binary(Operation::Mul, linear, inv_sqrt(disc)),
+                ),
+            )
+        } else {
+            // Real roots x = -p/2 ± sqrt(m) with m = -disc/4:
+            // (ln|x + p/2 - sqrt(m)| - ln|x + p/2 + sqrt(m)|) / (2 sqrt(m))
+            let m = Rational::zero()
+                .checked_sub(disc)?
+                .checked_div(Rational::new_from_int(4))?;
+            let sqrt = binary(
+                Operation::Pow,
+                Function::Rational(m.clone()),
+                rational(1, 2),
+            );
+            let shifted = poly_to_function(&vec![
+                p.checked_div(Rational::new_from_int(2))?,
+                Rational::new_from_int(1),
+            ]);
+            binary(
+                Operation::Mul,
+                binary(Operation::Mul, rational(1, 2), inv_sqrt(m)),
+                binary(
+                    Operation::Sub,
+                    ln_abs(binary(Operation::Sub, shifted.clone(), sqrt.clone())),
+                    ln_abs(binary(Operation::Add, shifted, sqrt)),
+                ),
+            )
+        };
+        result = binary(Operation::Add, result, log);
+        result = binary(
+            Operation::Add,
+            result,
+            binary(Operation::Mul, Function::Rational(rest), part),
+        );
+    }
+
+    Some(result)
+}
+
+/// Antiderivative of f(u) with u linear, still to be divided by the slope of u
+fn special_rule(kind: &FunctionType, exp: i32, argument: &Function) -> Option<Function> {
+    let formula = match (kind, exp) {
+        (FunctionType::Sin, 1) => "-cos(x)",
+        (FunctionType::Cos, 1) => "sin(x)",
+        (FunctionType::Tan, 1) => "-ln(abs(cos(x)))",
+        (FunctionType::Cot, 1) => "ln(abs(sin(x)))",
+        (FunctionType::Sec, 1) => "ln(abs(sec(x)+tan(x)))",
+        (FunctionType::Csc, 1) => "-ln(abs(csc(x)+cot(x)))",
+        (FunctionType::ASin, 1) => "xasin(x)+(1-x^2)^(1/2)",
+        (FunctionType::ACos, 1) => "xacos(x)-(1-x^2)^(1/2)",
+        (FunctionType::ATan, 1) => "xatan(x)-ln(1+x^2)/2",
+        (FunctionType::Sinh, 1) => "cosh(x)",
+        (FunctionType::Cosh, 1) => "sinh(x)",
+        (FunctionType::Tanh, 1) => "ln(cosh(x))",
+        (FunctionType::Coth, 1) => "ln(abs(sinh(x)))",
+        (FunctionType::Sech, 1) => "atan(sinh(x))",
+        (FunctionType::Csch, 1) => "ln(abs(tanh(x/2)))",
+        (FunctionType::ASinh, 1) => "xasinh(x)-(x^2+1)^(1/2)",
+        (FunctionType::ACosh, 1) => "xacosh(x)-(x^2-1)^(1/2)",
+        (FunctionType::ATanh, 1) => "xatanh(x)+ln(1-x^2)/2",
+        (FunctionType::Abs, 1) => "xabs(x)/2",
+        (FunctionType::Ln, 1) => "xln(x)-x",
+        (FunctionType::Sin, 2) => "x/2-sin(2x)/4",
+        (FunctionType::Cos, 2) => "x/2+sin(2x)/4",
+        (FunctionType::Tan, 2) => "tan(x)-x",
+        (FunctionType::Cot, 2) => "-cot(x)-x",
+        (FunctionType::Sec, 2) => "tan(x)",
+        (FunctionType::Csc, 2) => "-cot(x)",
+        (FunctionType::Tanh, 2) => "x-tanh(x)",
+        (FunctionType::Coth, 2) => "x-coth(x)",
+        (FunctionType::Sech, 2) => "tanh(x)",
+        (FunctionType::Csch, 2) => "-coth(x)",
+        (FunctionType::Ln, 2) => "xln(x)^2-2xln(x)+2x",
+        _ => return None,
+    };
+    Some(rule(formula, argument))
+}
+
+/// Antiderivative of base^exp when the variable appears linearly
+fn power_rule(base: &Function, exp: &Function) -> Option<Function> {
+    let (result, slope) = match (base, exp) {
+        (Function::Special { kind, argument }, Function::Rational(exp)) if exp.is_integer() => {
+            let slope = linear(argument)?;
+            (special_rule(kind, exp.num(), argument)?, slope)
+        }
+        (base, Function::Rational(exp)) => {
+            let slope = linear(base)?;
+            if *exp == -1 {
+                (ln_abs(base.clone()), slope)
+            } else {
+                let exp = exp.clone().checked_add(Rational::new_from_int(1))?;
+                let power = binary(
+                    Operation::Pow,
+                    base.clone(),
+                    Function::Rational(exp.clone()),
+                );
+                (power, slope.checked_mul(exp)?)
+            }
+        }
+        // c^u = e^(u ln(c)) for positive constants
+        (Function::E | Function::PI, exp) => {
+            let slope = linear(exp)?;
+            let ln = special(FunctionType::Ln, base.clone());
+            let power = binary(Operation::Pow, base.clone(), exp.clone());
+            (binary(Operation::Div, power, ln), slope)
+        }
+        (Function::Rational(val), exp) if val.num() > 0 && *val != 1 => {
+            let slope = linear(exp)?;
+            let ln = special(FunctionType::Ln, base.clone());
+            let power = binary(Operation::Pow, base.clone(), exp.clone());
+            (binary(Operation::Div, power, ln), slope)
+        }
+        _ => return None,
+    };
+    Some(binary(Operation::Div, result, Function::Rational(slope)))
+}
+
+/// Integration by parts of x^n * base^exp
+fn by_parts(n: i32, base: &Function, exp: &Function, depth: u8) -> Option<Function> {
+    if n > 12 {
+        return None;
+    }
+    let x_pow = |n: i32| binary(Operation::Pow, Function::X, rational(n, 1));
+    let factor = binary(Operation::Pow, base.clone(), exp.clone());
+
+    match (base, exp) {
+        // The factor is differentiated: x^(n+1)/(n+1) f - ∫ x^(n+1)/(n+1) f'
+        (
+            Function::Special {
+                kind:
+                    FunctionType::Ln
+                    | FunctionType::ATan
+                    | FunctionType::ATanh
+                    | FunctionType::ASin
+                    | FunctionType::ACos
+                    | FunctionType::ASinh
+                    | FunctionType::ACosh,
+                ..
+            },
+            Function::Rational(exp),
+        ) if *exp == 1 => {
+            let primitive = binary(Operation::Div, x_pow(n + 1), rational(n + 1, 1));
+            let rest = binary(Operation::Mul, primitive.clone(), base.derivative(1, 0, 0));
+            Some(binary(
+                Operation::Sub,
+                binary(Operation::Mul, primitive, factor),
+                integrate(&rest, depth + 1)?,
+            ))
+        }
+        // The polynomial is differentiated until it vanishes
+        (
+            Function::E
+            | Function::PI
+            | Function::Rational(_)
+            | Function::Special {
+                kind:
+                    FunctionType::Sin | FunctionType::Cos | FunctionType::Sinh | FunctionType::Cosh,
+                ..
+            },
+            _,
+        ) => {
+            let mut result = Function::Rational(Rational::zero());
+            let mut primitive = factor;
+            let mut coeff = 1;
+            for k in 0..=n {
+                primitive = integrate(&primitive, depth + 1)?;
+                let sign = if k % 2 == 0 { 1 } else { -1 };
+                let term = binary(
+                    Operation::Mul,
+                    binary(Operation::Mul, rational(sign * coeff, 1), x_pow(n - k)),
+                    primitive.clone(),
+                );
+                result = binary(Operation::Add, result, term);
+                coeff *= n - k;
+            }
+            Some(result)
+        }
+        _ => None,
+    }
+}
+
+/// Looks for an inner function u such that func = g(u) u'
+fn substitution(func: &Function, factors: &[(Function, Function)], depth: u8) -> Option<Function> {
+    let mut candidates: Vec<Function> = Vec::new();
+    for (base, exp) in factors {
+        candidates.push(base.clone());
+        if let Function::Special { argument, .. } = base {
+            candidates.push(*argument.clone());
+        }
+        candidates.push(exp.clone());
+    }
+
+    let mut tried = Vec::new();
+    for inner in candidates {
+        if inner == Function::X || !contains_variable(&inner) || tried.contains(&inner) {
+            continue;
+        }
+        tried.push(inner.clone());
+
+        let derivative = inner.derivative(1, 0, 0).simplify();
+        if derivative == Function::Rational(Rational::zero()) {
+            continue;
+        }
+        let outer = binary(Operation::Div, func.clone(), derivative).simplify();
+        let outer = outer.replace(&inner, &Function::Y);
+        if contains_x(&outer) {
+            continue;
+        }
+        let outer = outer.replace(&Function::Y, &Function::X);
+        if let Some(result) = integrate(&outer, depth + 1) {
+            return Some(result.replace(&Function::X, &inner));
+        }
+    }
+    None
+}
+
+fn integrate_term(term: &Term, depth: u8) -> Option<Function> {
+    let (constant, factors): (Vec<_>, Vec<_>) = term
+        .factors
+        .iter()
+        .cloned()
+        .partition(|(base, exp)| !contains_variable(base) && !contains_variable(exp));
+    let coeff = Term {
+        coeff: term.coeff.clone(),
+        factors: constant,
+    };
+    let rest = Term {
+        coeff: Rational::new_from_int(1),
+        factors,
+    };
+    let func = rest.to_function();
+
+    let result = match as_rational(&func) {
+        Some((num, den)) => integrate_rational(&num, &den),
+        None => match rest.factors.as_slice() {
+            [(base, exp)] => power_rule(base, exp),
+            [(Function::X, Function::Rational(n)), (base, exp)]
+            | [(base, exp), (Function::X, Function::Rational(n))]
+                if n.is_integer() && n.num() > 0 =>
+            {
+                by_parts(n.num(), base, exp, depth)
+            }
+            _ => None,
+        }
+        .or_else(|| substitution(&func, &rest.factors, depth)),
+    }?;
+
+    Some(binary(Operation::Mul, coeff.to_function(), result))
+}
+
+fn integrate(func: &Function, depth: u8) -> Option<Function> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    // A denominator with several quadratic factors can still be split term by term
+    if let Some(result) = as_rational(func).and_then(|(num, den)| integrate_rational(&num, &den)) {
+        return Some(result);
+    }
+
+    let mut result = Function::Rational(Rational::zero());
+    for term in normalize(func, true) {
+        result = binary(Operation::Add, result, integrate_term(&term, depth)?);
+    }
+    Some(result)
+}
+
+impl F1D {
+    /// Computes an antiderivative (with no integration constant) of F1D
+    /// ```
+    /// use ruut_functions::{F1D, integration::IntegrationError};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("2xcos(x^2)").unwrap();
+    /// assert_eq!(func.antiderivative().unwrap(), F1D::from_str("sin(x^2)").unwrap());
+    ///
+    /// let func = F1D::from_str("e^(x^2)").unwrap();
+    /// assert_eq!(
+    ///     func.antiderivative(),
+    ///     Err(IntegrationError::NoClosedForm("e^(x^2)".to_string()))
+    /// );
+    /// ```
+    pub fn antiderivative(&self) -> Result<Self, IntegrationError> {
+        match integrate(&self.0, 0) {
+            Some(func) => Ok(F1D(func.simplify())),
+            None => Err(IntegrationError::NoClosedForm(self.to_string())),
+        }
+    }
+}
+
+#[test]
+fn test_antiderivative() {
+    let cases = [
+        "3x^2-4x+7",
+        "x^(1/2)+x^(-3)+(2x+1)^(2/3)",
+        "1/x+1/(3x-2)",
+        "sin(2x)+cos(x/3)+tan(x)+cot(x)+sec(x)+csc(x)",
+        "asin(x/2)+acos(x/2)+atan(3x)",
+        "sinh(x)+cosh(2x)+tanh(x)+coth(x)+sech(x)+csch(x)",
+        "asinh(x)+acosh(x+2)+atanh(x/2)+abs(x-1)+ln(2x)",
+        "sin(x)^2+cos(x)^2+sec(x)^2+tan(x)^2+sech(x)^2+ln(x)^2",
+        "e^x+e^(3x-1)+2^x+pi^(x/2)",
+        "x^2e^x+xsin(x)+x^3cos(2x)+xcosh(x)+2^xx",
+        "xln(x)+x^2atan(x)",
+        "2xcos(x^2)+xe^(x^2)+sin(x)cos(x)+ln(x)/x+x(x^2+1)^(1/2)",
+        "1/(xln(x))+cos(x)e^sin(x)+x^3e^(x^2)",
+        "(x+1)/(x^2-1)+1/(x^2+1)+(2x+3)/(x^2+4x+5)",
+        "x^3/(x^2-3x+2)+1/(x^2-2)+1/(x(x-1)^2)",
+        "(x^4+1)/(x^3+x)",
+    ];
+    let points = [0.35, 0.7, 1.3, 2.6];
+
+    for func in cases {
+        let func = F1D::from_str(func).unwrap();
+        let antiderivative = func.antiderivative().unwrap_or_else(|err| panic!("{err}"));
+        let derivative = antiderivative.derivative();
+        for x in points {
+            let (value, expected) = (derivative.eval(x), func.eval(x));
+            if expected.is_finite() {
+                assert!(
+                    (value - expected).abs() < 1e-8 * expected.abs().max(1.),
+                    "{func} at {x}: {antiderivative}"
+                );
+            }
+        }
+    }
+
+    for func in ["e^(x^2)", "sin(x)/x", "x^x"] {
+        let func = F1D::from_str(func).unwrap();
+        assert!(matches!(
+            func.antiderivative(),
+            Err(IntegrationError::NoClosedForm(_))
+        ));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::algebra::rational::Rational;
+use crate::simplify::binary;
+use crate::solve::{finalize, isolate, occurrences, wrap, Condition, Raw};
+use crate::{Function, Operation, F1D};
+use std::error::Error;
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq)]
+/// Inverse of a F1D
+pub struct Inverse {
+    /// The inverse function
+    pub function: F1D,
+    /// Conditions on x for the inverse to be valid, empty if it's valid wherever it's defined.
+    /// Fractional powers are only evaluated for non negative bases, so the inverse of x^(1/3)
+    /// is x^3 for x >= 0, while the one of x^3 is valid on all the reals
+    pub domain: Vec<Condition<F1D>>,
+    /// The inverse has more than one branch and this is the principal one, e.g. asin(x) for
+    /// sin(x) or x^(1/2) for x^2
+    pub principal_branch: bool,
+}
+
+#[derive(Debug, PartialEq)]
+/// Errors that can occur while inverting a function
+pub enum InverseError {
+    /// x appears more than once, or in a piece that can't be inverted
+    NotInvertible(String),
+}
+
+impl Display for InverseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotInvertible(func) => write!(f, "Can't invert {func} symbolically"),
+        }
+    }
+}
+impl Error for InverseError {}
+
+impl F1D {
+    /// Computes the inverse function, solving y = f(x) for x and renaming y to x. x must appear
+    /// once, inside affine maps, rational powers, exponentials, logarithms and the invertible
+    /// functions of `FunctionType`
+    /// ```
+    /// use ruut_functions::{F1D, solve::Condition};
+    /// use std::str::FromStr;
+    ///
+    /// let celsius_to_fahrenheit = F1D::from_str("9x/5+32").unwrap();
+    /// let inverse = celsius_to_fahrenheit.inverse().unwrap();
+    /// assert_eq!(inverse.function.eval(212.), 100.);
+    /// assert!(inverse.domain.is_empty());
+    ///
+    /// let inverse = F1D::from_str("e^(2x)+1").unwrap().inverse().unwrap();
+    /// assert_eq!(inverse.function.to_string(), "ln(x-1)/2");
+    /// assert_eq!(
+    ///     inverse.domain,
+    ///     vec![Condition::Positive(F1D::from_str("x-1").unwrap())]
+    /// );
+    /// ```
+    pub fn inverse(&self) -> Result<Inverse, InverseError> {
+        let not_invertible = || InverseError::NotInvertible(self.to_string());
+        let zero = || Function::Rational(Rational::zero());
+
+        // Simplifying could merge repeated occurrences
+        let solutions = [self.0.clone(), self.0.simplify()]
+            .iter()
+            .filter(|func| occurrences(func, &Function::X) == 1)
+            .find_map(|func| {
+                let equation = binary(Operation::Sub, func.clone(), Function::Y);
+                isolate(&equation, zero(), &Function::X, Vec::new(), None)
+            })
+            .ok_or_else(not_invertible)?;
+
+        let branches = solutions.len();
+        let principal = solutions.into_iter().next().ok_or_else(not_invertible)?;
+        let periodic = principal.period.is_some();
+        let renamed = wrap(
+            Raw {
+                period: None,
+                ..principal
+            },
+            |func| func.replace(&Function::Y, &Function::X),
+        );
+        let inverse = wrap(finalize(renamed).ok_or_else(not_invertible)?, F1D);
+
+        Ok(Inverse {
+            function: inverse.value,
+            domain: inverse.conditions,
+            principal_branch: branches > 1 || periodic,
+        })
+    }
+}
+
+#[test]
+fn test_inverse() {
+    use std::str::FromStr;
+
+    // f(inverse(x)) = x on the domain, and inverse(f(x)) = x on the principal branch
+    let cases = [
+        ("3x-2", false, vec![-4., 0., 7.5]),
+        ("1/(x+1)", false, vec![-3., 0.5, 2.]),
+        ("x^3", false, vec![-2., -0.5, 0., 0.5, 2.]),
+        ("(x-1)^(5/3)", false, vec![1., 2., 9.]),
+        ("x^(-3)", false, vec![-2., -0.5, 0.5, 2.]),
+        ("x^2+1", true, vec![0.5, 3.]),
+        ("2^x", false, vec![-1., 0.5, 3.]),
+        ("ln(2x+1)", false, vec![0.1, 4.]),
+        ("sin(x)/2", true, vec![-1., 0.3, 1.2]),
+        ("tanh(x-1)", false, vec![-1., 1., 3.]),
+        ("asinh(x^(1/3))", false, vec![0.2, 8.]),
+        ("cosh(x)", true, vec![0.5, 2.]),
+    ];
+    for (func, principal_branch, points) in cases {
+        let func = F1D::from_str(func).unwrap();
+        let inverse = func.inverse().unwrap();
+        assert_eq!(inverse.principal_branch, principal_branch, "{func}");
+        for x in points {
+            let y = func.eval(x);
+            assert!((inverse.function.eval(y) - x).abs() < 1e-9, "{func} {x}");
+            assert!((func.eval(inverse.function.eval(y)) - y).abs() < 1e-9);
+        }
+    }
+
+    // Odd roots are defined on all the reals
+    let inverse = F1D::from_str("x^3").unwrap().inverse().unwrap();
+    assert!(inverse.domain.is_empty());
+    assert_eq!(inverse.function.eval(-8.), -2.);
+
+    // Fractional powers only take non negative values
+    for (func, y) in [("x^(1/3)", -8.), ("asinh(x^(1/3))", -2.)] {
+        let func = F1D::from_str(func).unwrap();
+        let inverse = func.inverse().unwrap();
+        assert!(func.eval(inverse.function.eval(y)).is_nan());
+        assert!(inverse.domain.iter().any(|condition| match condition {
+            Condition::NonNegative(bound) => bound.eval(y) < 0.,
+            _ => false,
+        }));
+    }
+    let inverse = F1D::from_str("x^(1/3)").unwrap().inverse().unwrap();
+    assert_eq!(inverse.function, F1D::from_str("x^3").unwrap());
+    assert_eq!(
+        inverse.domain,
+        vec![Condition::NonNegative(F1D::from_str("x").unwrap())]
+    );
+
+    let inverse = F1D::from_str("x^(1/2)").unwrap().inverse().unwrap();
+    assert_eq!(inverse.function, F1D::from_str("x^2").unwrap());
+    assert_eq!(
+        inverse.domain,
+        vec![Condition::NonNegative(F1D::from_str("x").unwrap())]
+    );
+
+    let inverse = F1D::from_str("acos(x)").unwrap().inverse().unwrap();
+    assert_eq!(inverse.function, F1D::from_str("cos(x)").unwrap());
+    assert_eq!(
+        inverse.domain,
+        vec![Condition::Between(
+            F1D::from_str("x").unwrap(),
+            F1D::from_str("0").unwrap(),
+            F1D::from_str("pi").unwrap()
+        )]
+    );
+
+    for func in ["x+sin(x)", "xe^x", "5"] {
+        let func = F1D::from_str(func).unwrap();
+        assert_eq!(
+            func.inverse(),
+            Err(InverseError::NotInvertible(func.to_string()))
+        );
+    }
+}
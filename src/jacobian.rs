@@ -0,0 +1,287 @@
+use crate::{Function, Matrix, Vec2, Vec3, F2D, F3D};
+
+/// Rows of partial derivatives of each function by the first `vars` variables
+fn jacobian(funcs: &[&Function], vars: usize) -> Vec<Function> {
+    funcs
+        .iter()
+        .flat_map(|func| (0..vars).map(move |j| func.derivative_by(j).simplify()))
+        .collect()
+}
+
+impl Matrix<F2D> {
+    /// Jacobian matrix of a map from R^2 to R^m, one row per function
+    /// ```
+    /// use ruut_functions::{F2D, Matrix};
+    /// use std::str::FromStr;
+    ///
+    /// let funcs = [F2D::from_str("xy").unwrap(), F2D::from_str("x+y").unwrap(), F2D::from_str("y^2").unwrap()];
+    /// let jacobian = Matrix::<F2D>::jacobian(&funcs);
+    ///
+    /// assert_eq!(jacobian.eval(2., 3.), Matrix::new(vec![3., 2., 1., 1., 0., 6.], 3, 2));
+    /// ```
+    pub fn jacobian(funcs: &[F2D]) -> Self {
+        let funcs: Vec<_> = funcs.iter().map(|func| &func.0).collect();
+        Matrix::new(
+            jacobian(&funcs, 2).into_iter().map(F2D).collect(),
+            funcs.len(),
+            2,
+        )
+    }
+
+    /// Calculate the determinant symbolically
+    /// ```
+    /// use ruut_functions::{F2D, Matrix};
+    /// use std::str::FromStr;
+    ///
+    /// let mat = Matrix::new(
+    ///     vec![
+    ///         F2D::from_str("x").unwrap(),
+    ///         F2D::from_str("y").unwrap(),
+    ///         F2D::from_str("y").unwrap(),
+    ///         F2D::from_str("x").unwrap(),
+    ///     ],
+    ///     2,
+    ///     2,
+    /// );
+    ///
+    /// assert_eq!(mat.determinant(), F2D::from_str("x^2-y^2").unwrap().simplify());
+    /// ```
+    pub fn determinant(&self) -> F2D {
+        let mat: Vec<_> = self.as_slice().iter().map(|func| func.0.clone()).collect();
+        F2D(Matrix::new(mat, self.n_row(), self.n_col())
+            .determinant()
+            .simplify())
+    }
+}
+
+impl Matrix<F3D> {
+    /// Jacobian matrix of a map from R^3 to R^m, one row per function
+    /// ```
+    /// use ruut_functions::{F3D, Matrix};
+    /// use std::str::FromStr;
+    ///
+    /// let funcs = [F3D::from_str("xyz").unwrap(), F3D::from_str("x+z").unwrap()];
+    /// let jacobian = Matrix::<F3D>::jacobian(&funcs);
+    ///
+    /// assert_eq!(jacobian.eval(1., 2., 3.), Matrix::new(vec![6., 3., 2., 1., 0., 1.], 2, 3));
+    /// ```
+    pub fn jacobian(funcs: &[F3D]) -> Self {
+        let funcs: Vec<_> = funcs.iter().map(|func| &func.0).collect();
+        Matrix::new(
+            jacobian(&funcs, 3).into_iter().map(F3D).collect(),
+            funcs.len(),
+            3,
+        )
+    }
+
+    /// Calculate the determinant symbolically
+    /// ```
+    /// use ruut_functions::{F3D, Matrix};
+    /// use std::str::FromStr;
+    ///
+    /// let mat = Matrix::new(
+    ///     ["x", "0", "0", "0", "y", "0", "0", "1", "z"]
+    ///         .iter()
+    ///         .map(|entry| F3D::from_str(entry).unwrap())
+    ///         .collect(),
+    ///     3,
+    ///     3,
+    /// );
+    ///
+    /// assert_eq!(mat.determinant(), F3D::from_str("xyz").unwrap());
+    /// ```
+    pub fn determinant(&self) -> F3D {
+        let mat: Vec<_> = self.as_slice().iter().map(|func| func.0.clone()).collect();
+        F3D(Matrix::new(mat, self.n_row(), self.n_col())
+            .determinant()
+            .simplify())
+    }
+}
+
+impl Vec2<F2D> {
+    /// Jacobian matrix of the map (x, y) -> (self.x, self.y)
+    /// ```
+    /// use ruut_functions::{F2D, Matrix, Vec2};
+    /// use std::str::FromStr;
+    ///
+    /// let map = Vec2 { x: F2D::from_str("x^2").unwrap(), y: F2D::from_str("xy").unwrap() };
+    ///
+    /// assert_eq!(map.jacobian().eval(1., 2.), Matrix::new(vec![2., 0., 2., 1.], 2, 2));
+    /// ```
+    pub fn jacobian(&self) -> Matrix<F2D> {
+        Matrix::new(
+            jacobian(&[&self.x.0, &self.y.0], 2)
+                .into_iter()
+                .map(F2D)
+                .collect(),
+            2,
+            2,
+        )
+    }
+
+    /// Determinant of the Jacobian, the local scale factor of areas in a change of variables
+    /// ```
+    /// use ruut_functions::{F2D, Vec2};
+    /// use std::str::FromStr;
+    ///
+    /// let map = Vec2 { x: F2D::from_str("2x+y").unwrap(), y: F2D::from_str("x-y").unwrap() };
+    ///
+    /// assert_eq!(map.jacobian_determinant(), F2D::from_str("-3").unwrap());
+    /// ```
+    pub fn jacobian_determinant(&self) -> F2D {
+        self.jacobian().determinant()
+    }
+}
+
+impl Vec3<F2D> {
+    /// Jacobian matrix of the map (x, y) -> (self.x, self.y, self.z), e.g. a parametrized surface
+    /// ```
+    /// use ruut_functions::{F2D, Matrix, Vec3};
+    /// use std::str::FromStr;
+    ///
+    /// let surface = Vec3 {
+    ///     x: F2D::from_str("x").unwrap(),
+    ///     y: F2D::from_str("y").unwrap(),
+    ///     z: F2D::from_str("x^2+y^2").unwrap(),
+    /// };
+    ///
+    /// assert_eq!(surface.jacobian().eval(1., 2.), Matrix::new(vec![1., 0., 0., 1., 2., 4.], 3, 2));
+    /// ```
+    pub fn jacobian(&self) -> Matrix<F2D> {
+        Matrix::new(
+            jacobian(&[&self.x.0, &self.y.0, &self.z.0], 2)
+                .into_iter()
+                .map(F2D)
+                .collect(),
+            3,
+            2,
+        )
+    }
+}
+
+impl Vec2<F3D> {
+    /// Jacobian matrix of the map (x, y, z) -> (self.x, self.y)
+    /// ```
+    /// use ruut_functions::{F3D, Matrix, Vec2};
+    /// use std::str::FromStr;
+    ///
+    /// let map = Vec2 { x: F3D::from_str("x+y+z").unwrap(), y: F3D::from_str("xz").unwrap() };
+    ///
+    /// assert_eq!(map.jacobian().eval(1., 1., 2.), Matrix::new(vec![1., 1., 1., 2., 0., 1.], 2, 3));
+    /// ```
+    pub fn jacobian(&self) -> Matrix<F3D> {
+        Matrix::new(
+            jacobian(&[&self.x.0, &self.y.0], 3)
+                .into_iter()
+                .map(F3D)
+                .collect(),
+            2,
+            3,
+        )
+    }
+}
+
+impl Vec3<F3D> {
+    /// Jacobian matrix of the map (x, y, z) -> (self.x, self.y, self.z)
+    /// ```
+    /// use ruut_functions::{F3D, Vec3};
+    /// use std::str::FromStr;
+    ///
+    /// let map = Vec3 {
+    ///     x: F3D::from_str("yz").unwrap(),
+    ///     y: F3D::from_str("xz").unwrap(),
+    ///     z: F3D::from_str("xy").unwrap(),
+    /// };
+    ///
+    /// assert!(map.jacobian().eval(1., 1., 1.).is_symmetric());
+    /// ```
+    pub fn jacobian(&self) -> Matrix<F3D> {
+        Matrix::new(
+            jacobian(&[&self.x.0, &self.y.0, &self.z.0], 3)
+                .into_iter()
+                .map(F3D)
+                .collect(),
+            3,
+            3,
+        )
+    }
+
+    /// Determinant of the Jacobian, the local scale factor of volumes in a change of variables
+    /// ```
+    /// use ruut_functions::{F3D, Vec3};
+    /// use std::str::FromStr;
+    ///
+    /// let map = Vec3 {
+    ///     x: F3D::from_str("2x").unwrap(),
+    ///     y: F3D::from_str("3y").unwrap(),
+    ///     z: F3D::from_str("z+x").unwrap(),
+    /// };
+    ///
+    /// assert_eq!(map.jacobian_determinant(), F3D::from_str("6").unwrap());
+    /// ```
+    pub fn jacobian_determinant(&self) -> F3D {
+        self.jacobian().determinant()
+    }
+}
+
+#[test]
+fn test_jacobian() {
+    use crate::trig::TrigForm;
+    use std::str::FromStr;
+
+    // Polar coordinates, x is the radius and y the angle
+    let polar = Vec2 {
+        x: F2D::from_str("xcos(y)").unwrap(),
+        y: F2D::from_str("xsin(y)").unwrap(),
+    };
+    assert_eq!(
+        polar
+            .jacobian_determinant()
+            .simplify_trig(TrigForm::Shortest),
+        F2D::from_str("x").unwrap()
+    );
+
+    // Spherical coordinates, x is the radius, y the polar angle and z the azimuth
+    let spherical = Vec3 {
+        x: F3D::from_str("xsin(y)cos(z)").unwrap(),
+        y: F3D::from_str("xsin(y)sin(z)").unwrap(),
+        z: F3D::from_str("xcos(y)").unwrap(),
+    };
+    let determinant = spherical.jacobian_determinant();
+    for (r, theta, phi) in [(1., 0.3, 2.), (2.5, 1.2, -0.4)] {
+        let expected = r * r * f64::sin(theta);
+        assert!((determinant.eval(r, theta, phi) - expected).abs() < 1e-12);
+    }
+
+    // Planar arm with two links of length 1, x and y are the joint angles
+    let arm = Vec2 {
+        x: F2D::from_str("cos(x)+cos(x+y)").unwrap(),
+        y: F2D::from_str("sin(x)+sin(x+y)").unwrap(),
+    };
+    let jacobian = arm.jacobian().eval(0.4, 0.9);
+    let (a, b) = (0.4, 0.4 + 0.9);
+    let expected = Matrix::new(
+        vec![
+            -f64::sin(a) - f64::sin(b),
+            -f64::sin(b),
+            f64::cos(a) + f64::cos(b),
+            f64::cos(b),
+        ],
+        2,
+        2,
+    );
+    for (value, expected) in jacobian.as_slice().iter().zip(expected.as_slice()) {
+        assert!((value - expected).abs() < 1e-12);
+    }
+    // Singular when the arm is stretched
+    assert!(arm.jacobian_determinant().eval(0.4, 0.).abs() < 1e-12);
+
+    let funcs = [
+        F3D::from_str("x").unwrap(),
+        F3D::from_str("y").unwrap(),
+        F3D::from_str("z").unwrap(),
+        F3D::from_str("x^2").unwrap(),
+    ];
+    let jacobian = Matrix::<F3D>::jacobian(&funcs);
+    assert_eq!((jacobian.n_row(), jacobian.n_col()), (4, 3));
+}
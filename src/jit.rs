@@ -0,0 +1,335 @@
+use crate::compiled::{Instr, Program};
+use crate::{FunctionType, Operation, F1D, F2D, F3D};
+use cranelift_codegen::ir::{types, AbiParam, InstBuilder, Value};
+use cranelift_codegen::settings::{self, Configurable};
+use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext};
+use cranelift_jit::{JITBuilder, JITModule};
+use cranelift_module::{default_libcall_names, Linkage, Module};
+use std::collections::HashMap;
+
+type NativeFn = extern "C" fn(f64, f64, f64) -> f64;
+
+macro_rules! helpers {
+    ($($name:ident => $method:ident),+) => {
+        $(extern "C" fn $name(x: f64) -> f64 {
+            x.$method()
+        })+
+
+        /// Functions the generated code calls for the operations without a native instruction
+        const HELPERS: &[(&str, extern "C" fn(f64) -> f64)] = &[$((stringify!($name), $name)),+];
+    };
+}
+
+helpers!(
+    ruut_ln => ln,
+    ruut_sin => sin,
+    ruut_cos => cos,
+    ruut_tan => tan,
+    ruut_asin => asin,
+    ruut_acos => acos,
+    ruut_atan => atan,
+    ruut_sinh => sinh,
+    ruut_cosh => cosh,
+    ruut_tanh => tanh,
+    ruut_asinh => asinh,
+    ruut_acosh => acosh,
+    ruut_atanh => atanh
+);
+
+extern "C" fn ruut_powf(base: f64, exp: f64) -> f64 {
+    base.powf(exp)
+}
+extern "C" fn ruut_powi(base: f64, exp: f64) -> f64 {
+    base.powi(exp as i32)
+}
+const BINARY_HELPERS: &[(&str, extern "C" fn(f64, f64) -> f64)] =
+    &[("ruut_powf", ruut_powf), ("ruut_powi", ruut_powi)];
+
+enum Code {
+    Native {
+        // Owns the memory the function lives in
+        module: Option<Box<JITModule>>,
+        func: NativeFn,
+    },
+    Interpreted(Program),
+}
+
+/// Function compiled to machine code, N is the number of variables.
+///
+/// If the function can't be compiled it's interpreted instead, see `is_native`
+pub struct JitFunction<const N: usize> {
+    code: Code,
+}
+
+impl<const N: usize> JitFunction<N> {
+    fn new(program: Program) -> Self {
+        let code = match compile(&program) {
+            Some((module, func)) => Code::Native {
+                module: Some(Box::new(module)),
+                func,
+            },
+            None => Code::Interpreted(program),
+        };
+        JitFunction { code }
+    }
+
+    /// Evaluates the function at the given point
+    pub fn eval(&self, point: [f64; N]) -> f64 {
+        let mut inputs = [0.; 3];
+        inputs[..N].copy_from_slice(&point);
+
+        match &self.code {
+            Code::Native { func, .. } => func(inputs[0], inputs[1], inputs[2]),
+            Code::Interpreted(program) => program.eval(&inputs),
+        }
+    }
+
+    /// Checks if the function was compiled to machine code
+    pub fn is_native(&self) -> bool {
+        matches!(self.code, Code::Native { .. })
+    }
+}
+
+impl<const N: usize> Drop for JitFunction<N> {
+    fn drop(&mut self) {
+        if let Code::Native { module, .. } = &mut self.code {
+            if let Some(module) = module.take() {
+                // SAFETY: the only pointer into the module is `func`, which is dropped with it
+                unsafe { module.free_memory() };
+            }
+        }
+    }
+}
+
+fn compile(program: &Program) -> Option<(JITModule, NativeFn)> {
+    let mut flags = settings::builder();
+    flags.set("opt_level", "speed").ok()?;
+    let isa = cranelift_native::builder()
+        .ok()?
+        .finish(settings::Flags::new(flags))
+        .ok()?;
+
+    let mut builder = JITBuilder::with_isa(isa, default_libcall_names());
+    for (name, helper) in HELPERS {
+        builder.symbol(*name, *helper as *const u8);
+    }
+    for (name, helper) in BINARY_HELPERS {
+        builder.symbol(*name, *helper as *const u8);
+    }
+    let mut module = JITModule::new(builder);
+
+    match define(&mut module, program) {
+        Some(func) => Some((module, func)),
+        None => {
+            // SAFETY: nothing was handed out from the module
+            unsafe { module.free_memory() };
+            None
+        }
+    }
+}
+
+fn define(module: &mut JITModule, program: &Program) -> Option<NativeFn> {
+    let mut ctx = module.make_context();
+    for _ in 0..3 {
+        ctx.func.signature.params.push(AbiParam::new(types::F64));
+    }
+    ctx.func.signature.returns.push(AbiParam::new(types::F64));
+
+    let mut unary = module.make_signature();
+    unary.params.push(AbiParam::new(types::F64));
+    unary.returns.push(AbiParam::new(types::F64));
+    let mut binary = unary.clone();
+    binary.params.push(AbiParam::new(types::F64));
+
+    let mut helpers = HashMap::new();
+    for (name, _) in HELPERS {
+        let id = module
+            .declare_function(name, Linkage::Import, &unary)
+            .ok()?;
+        helpers.insert(*name, id);
+    }
+    for (name, _) in BINARY_HELPERS {
+        let id = module
+            .declare_function(name, Linkage::Import, &binary)
+            .ok()?;
+        helpers.insert(*name, id);
+    }
+
+    let mut func_ctx = FunctionBuilderContext::new();
+    let mut builder = FunctionBuilder::new(&mut ctx.func, &mut func_ctx);
+    let block = builder.create_block();
+    builder.append_block_params_for_function_params(block);
+    builder.switch_to_block(block);
+    builder.seal_block(block);
+    let inputs = builder.block_params(block).to_vec();
+
+    let mut call = |builder: &mut FunctionBuilder, name: &str, args: &[Value]| {
+        let func = module.declare_func_in_func(helpers[name], builder.func);
+        let inst = builder.ins().call(func, args);
+        builder.inst_results(inst)[0]
+    };
+
+    let mut values: Vec<Value> = Vec::with_capacity(program.instrs.len());
+    for instr in &program.instrs {
+        let value = match instr {
+            Instr::Var(i) => inputs[*i],
+            Instr::Const(val) => builder.ins().f64const(*val),
+            Instr::Binary(operation, left, right) => {
+                let (left, right) = (values[*left], values[*right]);
+                match operation {
+                    Operation::Add => builder.ins().fadd(left, right),
+                    Operation::Sub => builder.ins().fsub(left, right),
+                    Operation::Mul => builder.ins().fmul(left, right),
+                    Operation::Div => builder.ins().fdiv(left, right),
+                    Operation::Pow => call(&mut builder, "ruut_powf", &[left, right]),
+                    Operation::Comp => return None,
+                }
+            }
+            Instr::Powi(base, exp) => {
+                let exp = builder.ins().f64const(*exp as f64);
+                call(&mut builder, "ruut_powi", &[values[*base], exp])
+            }
+            Instr::Special(kind, arg) => {
+                let arg = values[*arg];
+                let (name, reciprocal) = match kind {
+                    FunctionType::Abs => {
+                        values.push(builder.ins().fabs(arg));
+                        continue;
+                    }
+                    FunctionType::Ln => ("ruut_ln", false),
+                    FunctionType::Sin => ("ruut_sin", false),
+                    FunctionType::Cos => ("ruut_cos", false),
+                    FunctionType::Tan => ("ruut_tan", false),
+                    FunctionType::Cot => ("ruut_tan", true),
+                    FunctionType::Sec => ("ruut_cos", true),
+                    FunctionType::Csc => ("ruut_sin", true),
+                    FunctionType::ASin => ("ruut_asin", false),
+                    FunctionType::ACos => ("ruut_acos", false),
+                    FunctionType::ATan => ("ruut_atan", false),
+                    FunctionType::Sinh => ("ruut_sinh", false),
+                    FunctionType::Cosh => ("ruut_cosh", false),
+                    FunctionType::Tanh => ("ruut_tanh", false),
+                    FunctionType::Coth => ("ruut_tanh", true),
+                    FunctionType::Sech => ("ruut_cosh", true),
+                    FunctionType::Csch => ("ruut_sinh", true),
+                    FunctionType::ASinh => ("ruut_asinh", false),
+                    FunctionType::ACosh => ("ruut_acosh", false),
+                    FunctionType::ATanh => ("ruut_atanh", false),
+                };
+
+                let result = call(&mut builder, name, &[arg]);
+                if reciprocal {
+                    let one = builder.ins().f64const(1.);
+                    builder.ins().fdiv(one, result)
+                } else {
+                    result
+                }
+            }
+        };
+        values.push(value);
+    }
+
+    builder.ins().return_(&[*values.last()?]);
+    builder.finalize();
+
+    let id = module
+        .declare_function("function", Linkage::Local, &ctx.func.signature)
+        .ok()?;
+    module.define_function(id, &mut ctx).ok()?;
+    module.clear_context(&mut ctx);
+    module.finalize_definitions().ok()?;
+
+    let code = module.get_finalized_function(id);
+    // SAFETY: the function was defined with the (f64, f64, f64) -> f64 signature
+    Some(unsafe { std::mem::transmute::<*const u8, NativeFn>(code) })
+}
+
+impl F1D {
+    /// Compiles the function to machine code
+    /// ```
+    /// use ruut_functions::F1D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("x^2+sin(x)").unwrap().jit();
+    ///
+    /// assert!(func.is_native());
+    /// assert_eq!(func.eval([2.]), 4. + 2f64.sin());
+    /// ```
+    pub fn jit(&self) -> JitFunction<1> {
+        JitFunction::new(self.program())
+    }
+}
+impl F2D {
+    /// Compiles the function to machine code
+    /// ```
+    /// use ruut_functions::F2D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("xy+e^x").unwrap().jit();
+    ///
+    /// assert_eq!(func.eval([0., 3.]), 1.);
+    /// ```
+    pub fn jit(&self) -> JitFunction<2> {
+        JitFunction::new(self.program())
+    }
+}
+impl F3D {
+    /// Compiles the function to machine code
+    /// ```
+    /// use ruut_functions::F3D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("xyz").unwrap().jit();
+    ///
+    /// assert_eq!(func.eval([1., 2., 3.]), 6.);
+    /// ```
+    pub fn jit(&self) -> JitFunction<3> {
+        JitFunction::new(self.program())
+    }
+}
+
+#[test]
+fn test_jit() {
+    use std::str::FromStr;
+
+    let funcs = [
+        "x^2+y^3-z^(-2)",
+        "sin(xy)cos(yz)+tan(x)",
+        "cot(x)+sec(y)+csc(z)",
+        "asin(x/3)+acos(y/3)+atan(z)",
+        "sinh(x)cosh(y)tanh(z)",
+        "coth(x)+sech(y)+csch(z)",
+        "asinh(x)+acosh(y+2)+atanh(z/3)",
+        "ln(x^2+1)abs(y-z)",
+        "e^(xy)/(1+z^2)+pi",
+        "(x^2+1)^y+x^(1/3)",
+    ];
+    let points = [
+        [0.3, 0.7, 1.2],
+        [-1.1, 0.4, 2.5],
+        [2., -0.9, -0.6],
+        [0.05, 1.7, 0.9],
+    ];
+
+    for func in funcs {
+        let func = F3D::from_str(func).unwrap();
+        let compiled = func.jit();
+        assert!(compiled.is_native(), "{func}");
+
+        for point in points {
+            let expected = func.0.evaluate(&point);
+            let value = compiled.eval(point);
+            if expected.is_nan() {
+                assert!(value.is_nan(), "{func}");
+            } else {
+                assert!(
+                    (value - expected).abs() <= 1e-12 * expected.abs().max(1.),
+                    "{func} at {point:?}: {value} != {expected}"
+                );
+            }
+        }
+    }
+
+    let func = F1D::from_str("x^3").unwrap().jit();
+    assert_eq!(func.eval([-2.]), -8.);
+}
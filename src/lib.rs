@@ -2,13 +2,55 @@
 
 /// Polynomials
 pub mod algebra;
+/// Automatic differentiation
+pub mod autodiff;
+/// Code generation
+pub mod codegen;
+mod compiled;
+mod compose;
 /// Contains context
 pub mod context;
+/// Hash-consed expression graphs
+pub mod dag;
+mod derivatives;
+/// Equivalence checks
+pub mod equivalence;
+/// Exact evaluation
+pub mod exact;
+mod factor;
+mod implicit;
+/// Symbolic integration
+pub mod integration;
+/// Inverse functions
+pub mod inverse;
+mod jacobian;
+/// Compilation to machine code
+#[cfg(feature = "jit")]
+pub mod jit;
+/// Limits
+pub mod limit;
+/// Logarithm and exponential rules
+pub mod logexp;
 /// Operations
 pub mod operations;
 /// Operators
 pub mod operators;
+/// Critical points and their classification
+pub mod optimization;
 mod parser;
+/// Arbitrary-precision evaluation
+#[cfg(feature = "bigfloat")]
+pub mod precision;
+/// Generic numeric types for evaluation
+pub mod scalar;
+/// Series expansions
+pub mod series;
+mod simplify;
+/// Equation solving
+pub mod solve;
+/// Trigonometric identities
+pub mod trig;
+mod vector;
 use crate::parser::splitter::split;
 use crate::parser::{parse, ParsingError};
 pub use algebra::matrix::{Matrix, Vec2, Vec3};
@@ -17,7 +59,7 @@ use context::Context;
 use std::str::FromStr;
 
 /// Representation of a Function
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub(crate) enum Function {
     /// X Variable
     X,
@@ -57,7 +99,7 @@ pub struct F2D(Function);
 /// Representation of a function with 3 variables
 pub struct F3D(Function);
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 /// Types of special built-in functions
 pub(crate) enum FunctionType {
     /// Sine
@@ -102,7 +144,18 @@ pub(crate) enum FunctionType {
     Ln,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// Variables a function can depend on
+pub enum Variable {
+    /// X Variable
+    X,
+    /// Y Variable
+    Y,
+    /// Z Variable
+    Z,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub(crate) enum Operation {
     Add,
     Sub,
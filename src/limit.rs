@@ -0,0 +1,698 @@
+use crate::algebra::rational::Rational;
+use crate::operations::eval_trascendental;
+use crate::simplify::binary;
+use crate::trig::TrigForm;
+use crate::{Function, FunctionType, Operation, F1D};
+use std::error::Error;
+use std::fmt::Display;
+
+/// Point a limit is taken at
+#[derive(Debug, Clone, PartialEq)]
+pub enum Point {
+    /// Finite point
+    Finite(Rational),
+    /// Plus infinity
+    PosInfinity,
+    /// Minus infinity
+    NegInfinity,
+}
+
+/// Side the point is approached from, ignored at infinity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    /// From values smaller than the point
+    Left,
+    /// From values bigger than the point
+    Right,
+    /// Both sides, the one sided limits have to agree
+    Both,
+}
+
+/// Result of a limit
+#[derive(Debug, PartialEq)]
+pub enum Limit {
+    /// Exact finite value
+    Finite(F1D),
+    /// Plus infinity
+    PosInfinity,
+    /// Minus infinity
+    NegInfinity,
+    /// The function oscillates, isn't defined near the point or the one sided limits differ
+    DoesNotExist,
+}
+
+#[derive(Debug, PartialEq)]
+/// Errors that can occur while computing a limit
+pub enum LimitError {
+    /// The indeterminate form couldn't be resolved
+    Undetermined(String),
+}
+
+impl Display for LimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Undetermined(func) => write!(f, "Couldn't determine the limit of {func}"),
+        }
+    }
+}
+impl Error for LimitError {}
+
+/// How deep L'Hôpital's rule and the rewrites can recurse
+const MAX_DEPTH: u8 = 8;
+/// Highest derivative looked at for the leading term of a series
+const MAX_ORDER: i32 = 6;
+/// Constants smaller than this are considered zero
+const EPSILON: f64 = 1e-12;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Finite(Function),
+    PosInf,
+    NegInf,
+    /// Doesn't converge but stays bounded, like sin(x) at infinity
+    Oscillating,
+    /// Stays bounded, but may or may not converge, like sin(x)^2+cos(x)^2 at infinity
+    Bounded,
+    /// Not defined around the point
+    Undefined,
+}
+
+fn rational(num: i32, den: i32) -> Function {
+    Function::Rational(Rational::new(num, den))
+}
+
+fn numeric(constant: &Function) -> f64 {
+    constant.evaluate(&[0., 0., 0.])
+}
+
+fn is_zero(constant: &Function) -> bool {
+    numeric(constant).abs() < EPSILON
+}
+
+fn finite(constant: Function) -> Value {
+    Value::Finite(constant.simplify())
+}
+
+fn infinity(positive: bool) -> Value {
+    match positive {
+        true => Value::PosInf,
+        false => Value::NegInf,
+    }
+}
+
+/// Equivalent form without the pole of the given function
+fn rewrite(kind: &FunctionType, argument: &Function) -> Option<Function> {
+    let special = |kind| Function::Special {
+        kind,
+        argument: Box::new(argument.clone()),
+    };
+    let (num, den) = match kind {
+        FunctionType::Tan => (special(FunctionType::Sin), special(FunctionType::Cos)),
+        FunctionType::Cot => (special(FunctionType::Cos), special(FunctionType::Sin)),
+        FunctionType::Sec => (rational(1, 1), special(FunctionType::Cos)),
+        FunctionType::Csc => (rational(1, 1), special(FunctionType::Sin)),
+        FunctionType::Coth => (special(FunctionType::Cosh), special(FunctionType::Sinh)),
+        FunctionType::Csch => (rational(1, 1), special(FunctionType::Sinh)),
+        // atanh(u) = ln((1 + u)/(1 - u))/2
+        FunctionType::ATanh => {
+            let ratio = binary(
+                Operation::Div,
+                binary(Operation::Add, rational(1, 1), argument.clone()),
+                binary(Operation::Sub, rational(1, 1), argument.clone()),
+            );
+            let ln = Function::Special {
+                kind: FunctionType::Ln,
+                argument: Box::new(ratio),
+            };
+            (ln, rational(2, 1))
+        }
+        _ => return None,
+    };
+    Some(binary(Operation::Div, num, den))
+}
+
+/// One sided approach to a point
+struct Approach {
+    /// None at infinity
+    point: Option<Rational>,
+    /// 1 from the right or towards plus infinity, -1 otherwise
+    side: i32,
+}
+
+impl Approach {
+    /// Sign of the function close to the point, None if it isn't clear
+    fn sign_near(&self, func: &Function) -> Option<bool> {
+        let side = self.side as f64;
+        let samples = match &self.point {
+            Some(point) => {
+                let scale = point.eval().abs().max(1.);
+                [1e-6, 1e-9].map(|h| point.eval() + side * h * scale)
+            }
+            None => [1e6, 1e9].map(|x| side * x),
+        };
+        let [first, second] = samples.map(|x| func.evaluate(&[x, 0., 0.]));
+        match (first, second) {
+            (first, second) if first > 0. && second > 0. => Some(true),
+            (first, second) if first < 0. && second < 0. => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Replaces abs(u) with u or -u when the sign of u near the point is known
+    fn unfold_abs(&self, func: &Function) -> Function {
+        match func {
+            Function::Special {
+                kind: FunctionType::Abs,
+                argument,
+            } => {
+                let argument = self.unfold_abs(argument);
+                match self.sign_near(&argument) {
+                    Some(true) => argument,
+                    Some(false) => binary(Operation::Mul, rational(-1, 1), argument),
+                    None => Function::Special {
+                        kind: FunctionType::Abs,
+                        argument: Box::new(argument),
+                    },
+                }
+            }
+            Function::Special { kind, argument } => Function::Special {
+                kind: kind.clone(),
+                argument: Box::new(self.unfold_abs(argument)),
+            },
+            Function::Binary { operation, terms } => binary(
+                operation.clone(),
+                self.unfold_abs(&terms.0),
+                self.unfold_abs(&terms.1),
+            ),
+            _ => func.clone(),
+        }
+    }
+
+    fn limit(&self, func: &Function, depth: u8) -> Option<Value> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+
+        match func {
+            Function::X => Some(match &self.point {
+                Some(point) => Value::Finite(Function::Rational(point.clone())),
+                None => infinity(self.side > 0),
+            }),
+            Function::E | Function::PI | Function::Rational(_) => Some(Value::Finite(func.clone())),
+            Function::Y | Function::Z => None,
+            Function::Binary { operation, terms } => {
+                let (left, right) = (&*terms.0, &*terms.1);
+                match operation {
+                    Operation::Add => self.add(left, right, depth),
+                    Operation::Sub => {
+                        let right = binary(Operation::Mul, rational(-1, 1), right.clone());
+                        self.add(left, &right, depth)
+                    }
+                    Operation::Mul => self.mul(left, right, depth),
+                    Operation::Div => self.div(left, right, depth),
+                    Operation::Pow => self.pow(left, right, depth),
+                    Operation::Comp => None,
+                }
+            }
+            Function::Special { kind, argument } => self.special(kind, argument, depth),
+        }
+    }
+
+    fn add(&self, left: &Function, right: &Function, depth: u8) -> Option<Value> {
+        let (left_value, right_value) = (self.limit(left, depth)?, self.limit(right, depth)?);
+
+        Some(match (&left_value, &right_value) {
+            (Value::Undefined, _) | (_, Value::Undefined) => Value::Undefined,
+            (Value::Finite(first), Value::Finite(second)) => {
+                finite(binary(Operation::Add, first.clone(), second.clone()))
+            }
+            (Value::PosInf, Value::NegInf) | (Value::NegInf, Value::PosInf) => {
+                // left + right = left (1 + right/left)
+                let ratio = binary(Operation::Div, right.clone(), left.clone()).simplify();
+                match self.limit(&ratio, depth + 1)? {
+                    Value::Finite(ratio) => {
+                        let factor = numeric(&ratio) + 1.;
+                        if factor.abs() < EPSILON {
+                            // The infinities cancel, pulling out common factors may help
+                            let sum = binary(Operation::Add, left.clone(), right.clone());
+                            return match sum.factor() {
+                                Function::Binary {
+                                    operation: Operation::Add | Operation::Sub,
+                                    ..
+                                } => None,
+                                factored => self.limit(&factored, depth + 1),
+                            };
+                        }
+                        infinity((factor > 0.) == (left_value == Value::PosInf))
+                    }
+                    Value::PosInf | Value::NegInf => right_value,
+                    _ => return None,
+                }
+            }
+            (Value::PosInf | Value::NegInf, _) => left_value,
+            (_, Value::PosInf | Value::NegInf) => right_value,
+            (Value::Finite(_), Value::Oscillating) | (Value::Oscillating, Value::Finite(_)) => {
+                Value::Oscillating
+            }
+            // Two bounded terms can cancel each other's oscillations
+            _ => Value::Bounded,
+        })
+    }
+
+    fn mul(&self, left: &Function, right: &Function, depth: u8) -> Option<Value> {
+        let (left_value, right_value) = (self.limit(left, depth)?, self.limit(right, depth)?);
+
+        Some(match (&left_value, &right_value) {
+            (Value::Undefined, _) | (_, Value::Undefined) => Value::Undefined,
+            (Value::Finite(first), Value::Finite(second)) => {
+                finite(binary(Operation::Mul, first.clone(), second.clone()))
+            }
+            (Value::Finite(constant), inf @ (Value::PosInf | Value::NegInf))
+            | (inf @ (Value::PosInf | Value::NegInf), Value::Finite(constant)) => {
+                if !is_zero(constant) {
+                    infinity((numeric(constant) > 0.) == (*inf == Value::PosInf))
+                } else {
+                    // 0 * inf, either as inf / (1/0) or as 0 / (1/inf)
+                    let (zero, inf) = match left_value {
+                        Value::Finite(_) => (left, right),
+                        _ => (right, left),
+                    };
+                    let reciprocal =
+                        |func: &Function| binary(Operation::Div, rational(1, 1), func.clone());
+                    self.div(inf, &reciprocal(zero).simplify(), depth + 1)
+                        .or_else(|| self.div(zero, &reciprocal(inf).simplify(), depth + 1))?
+                }
+            }
+            (Value::PosInf | Value::NegInf, Value::PosInf | Value::NegInf) => {
+                infinity(left_value == right_value)
+            }
+            (Value::Finite(constant), bounded @ (Value::Oscillating | Value::Bounded))
+            | (bounded @ (Value::Oscillating | Value::Bounded), Value::Finite(constant)) => {
+                match is_zero(constant) {
+                    true => Value::Finite(rational(0, 1)),
+                    false => bounded.clone(),
+                }
+            }
+            (Value::Oscillating | Value::Bounded, Value::Oscillating | Value::Bounded) => {
+                Value::Bounded
+            }
+            _ => return None,
+        })
+    }
+
+    fn div(&self, num: &Function, den: &Function, depth: u8) -> Option<Value> {
+        let (num_value, den_value) = (self.limit(num, depth)?, self.limit(den, depth)?);
+
+        Some(match (&num_value, &den_value) {
+            (Value::Undefined, _) | (_, Value::Undefined) => Value::Undefined,
+            (Value::Finite(first), Value::Finite(second)) if !is_zero(second) => {
+                finite(binary(Operation::Div, first.clone(), second.clone()))
+            }
+            (Value::Finite(constant), Value::Finite(_)) if !is_zero(constant) => {
+                infinity((numeric(constant) > 0.) == self.sign_near(den)?)
+            }
+            (Value::Finite(_), Value::Finite(_)) => self
+                .series(num, den, depth)
+                .or_else(|| self.lhopital(num, den, depth))?,
+            (
+                Value::Finite(_) | Value::Oscillating | Value::Bounded,
+                Value::PosInf | Value::NegInf,
+            ) => Value::Finite(rational(0, 1)),
+            (Value::PosInf | Value::NegInf, Value::Finite(constant)) => {
+                let positive = match is_zero(constant) {
+                    true => self.sign_near(den)?,
+                    false => numeric(constant) > 0.,
+                };
+                infinity(positive == (num_value == Value::PosInf))
+            }
+            (Value::PosInf | Value::NegInf, Value::PosInf | Value::NegInf) => {
+                self.lhopital(num, den, depth)?
+            }
+            (Value::Oscillating | Value::Bounded, Value::Finite(constant))
+                if !is_zero(constant) =>
+            {
+                num_value
+            }
+            _ => return None,
+        })
+    }
+
+    fn lhopital(&self, num: &Function, den: &Function, depth: u8) -> Option<Value> {
+        let ratio = binary(
+            Operation::Div,
+            num.derivative(1, 0, 0),
+            den.derivative(1, 0, 0),
+        );
+        self.limit(&ratio.simplify(), depth + 1)
+    }
+
+    /// Compares the leading terms of the Taylor series of two functions vanishing at the point
+    fn series(&self, num: &Function, den: &Function, depth: u8) -> Option<Value> {
+        self.point.as_ref()?;
+        let (num_order, num_coeff) = self.leading(num, depth)?;
+        let (den_order, den_coeff) = self.leading(den, depth)?;
+        let ratio = numeric(&num_coeff) / numeric(&den_coeff);
+
+        Some(match num_order - den_order {
+            0 => finite(binary(Operation::Div, num_coeff, den_coeff)),
+            diff if diff > 0 => Value::Finite(rational(0, 1)),
+            // ratio * (x - a)^diff
+            diff => infinity((ratio > 0.) == (diff % 2 == 0 || self.side > 0)),
+        })
+    }
+
+    /// Order and coefficient of the first non vanishing term of the Taylor series at the point
+    fn leading(&self, func: &Function, depth: u8) -> Option<(i32, Function)> {
+        let mut derivative = func.clone();
+        let mut factorial = 1;
+        for order in 1..=MAX_ORDER {
+            derivative = derivative.derivative(1, 0, 0).simplify();
+            factorial *= order;
+            match self.limit(&derivative, depth + 1)? {
+                Value::Finite(val) if is_zero(&val) => continue,
+                Value::Finite(val) => {
+                    return Some((order, binary(Operation::Div, val, rational(factorial, 1))))
+                }
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    fn pow(&self, base: &Function, exp: &Function, depth: u8) -> Option<Value> {
+        let base_value = self.limit(base, depth)?;
+
+        if let Function::Rational(exp) = exp {
+            let positive = exp.num() > 0;
+            let even = exp.is_integer() && exp.num() % 2 == 0;
+            return Some(match base_value {
+                Value::Undefined => Value::Undefined,
+                _ if *exp == 0 => Value::Finite(rational(1, 1)),
+                Value::Finite(val) if !is_zero(&val) => {
+                    if numeric(&val) < 0. && !exp.is_integer() {
+                        Value::Undefined
+                    } else {
+                        finite(binary(Operation::Pow, val, Function::Rational(exp.clone())))
+                    }
+                }
+                Value::Finite(_) => {
+                    let sign = self.sign_near(base)?;
+                    if !sign && !exp.is_integer() {
+                        Value::Undefined
+                    } else if positive {
+                        Value::Finite(rational(0, 1))
+                    } else {
+                        infinity(sign || even)
+                    }
+                }
+                Value::PosInf if positive => Value::PosInf,
+                Value::NegInf if positive && exp.is_integer() => infinity(even),
+                Value::NegInf if positive => Value::Undefined,
+                Value::PosInf | Value::NegInf => Value::Finite(rational(0, 1)),
+                Value::Oscillating | Value::Bounded if positive && exp.is_integer() => {
+                    Value::Bounded
+                }
+                Value::Oscillating | Value::Bounded => return None,
+            });
+        }
+
+        let exp_value = self.limit(exp, depth)?;
+        if let (Value::Finite(base), Value::Finite(exp)) = (&base_value, &exp_value) {
+            if numeric(base) > 0. {
+                return Some(finite(binary(Operation::Pow, base.clone(), exp.clone())));
+            }
+        }
+
+        // base^exp = e^(exp ln(base))
+        let ln = Function::Special {
+            kind: FunctionType::Ln,
+            argument: Box::new(base.clone()),
+        };
+        Some(match self.mul(exp, &ln, depth + 1)? {
+            Value::Finite(val) => finite(binary(Operation::Pow, Function::E, val)),
+            Value::PosInf => Value::PosInf,
+            Value::NegInf => Value::Finite(rational(0, 1)),
+            value => value,
+        })
+    }
+
+    fn special(&self, kind: &FunctionType, argument: &Function, depth: u8) -> Option<Value> {
+        let value = self.limit(argument, depth)?;
+
+        Some(match value {
+            Value::Undefined => Value::Undefined,
+            Value::Finite(val) => {
+                if eval_trascendental(kind, numeric(&val)).is_finite() {
+                    finite(Function::Special {
+                        kind: kind.clone(),
+                        argument: Box::new(val),
+                    })
+                } else if let (FunctionType::Ln, true) = (kind, is_zero(&val)) {
+                    match self.sign_near(argument)? {
+                        true => Value::NegInf,
+                        false => Value::Undefined,
+                    }
+                } else if let Some(rewritten) = rewrite(kind, argument) {
+                    self.limit(&rewritten, depth + 1)?
+                } else {
+                    Value::Undefined
+                }
+            }
+            Value::PosInf | Value::NegInf => {
+                let positive = value == Value::PosInf;
+                let sign = if positive { 1 } else { -1 };
+                match kind {
+                    FunctionType::Ln | FunctionType::ACosh if !positive => Value::Undefined,
+                    FunctionType::Ln | FunctionType::ACosh => Value::PosInf,
+                    FunctionType::Sin | FunctionType::Cos => Value::Oscillating,
+                    FunctionType::ATan => {
+                        finite(binary(Operation::Mul, rational(sign, 2), Function::PI))
+                    }
+                    FunctionType::Sinh | FunctionType::ASinh => infinity(positive),
+                    FunctionType::Cosh | FunctionType::Abs => Value::PosInf,
+                    FunctionType::Tanh | FunctionType::Coth => Value::Finite(rational(sign, 1)),
+                    FunctionType::Sech | FunctionType::Csch => Value::Finite(rational(0, 1)),
+                    // Unbounded oscillation or outside of the domain
+                    _ => Value::Undefined,
+                }
+            }
+            // Injective functions keep the oscillation, the others could remove it
+            Value::Oscillating | Value::Bounded => match kind {
+                FunctionType::ATan
+                | FunctionType::Tanh
+                | FunctionType::Sinh
+                | FunctionType::ASinh => value,
+                FunctionType::Sin
+                | FunctionType::Cos
+                | FunctionType::Sech
+                | FunctionType::Cosh
+                | FunctionType::Abs => Value::Bounded,
+                _ => return None,
+            },
+        })
+    }
+}
+
+impl F1D {
+    /// Computes the limit of F1D at a point, resolving indeterminate forms with series expansion
+    /// and L'Hôpital's rule
+    /// ```
+    /// use ruut_functions::{F1D, limit::{Direction, Limit, Point}};
+    /// use ruut_functions::algebra::rational::Rational;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("sin(x)/x").unwrap();
+    /// let zero = Point::Finite(Rational::zero());
+    /// assert_eq!(
+    ///     func.limit(zero.clone(), Direction::Both).unwrap(),
+    ///     Limit::Finite(F1D::from_str("1").unwrap())
+    /// );
+    ///
+    /// let func = F1D::from_str("1/x").unwrap();
+    /// assert_eq!(func.limit(zero.clone(), Direction::Left).unwrap(), Limit::NegInfinity);
+    /// assert_eq!(func.limit(zero, Direction::Both).unwrap(), Limit::DoesNotExist);
+    /// ```
+    pub fn limit(&self, point: Point, direction: Direction) -> Result<Limit, LimitError> {
+        let approaches = match (point, direction) {
+            (Point::PosInfinity, _) => vec![(None, 1)],
+            (Point::NegInfinity, _) => vec![(None, -1)],
+            (Point::Finite(point), Direction::Left) => vec![(Some(point), -1)],
+            (Point::Finite(point), Direction::Right) => vec![(Some(point), 1)],
+            (Point::Finite(point), Direction::Both) => {
+                vec![(Some(point.clone()), -1), (Some(point), 1)]
+            }
+        };
+
+        // The written form first, canonical forms can hide the cancellations. A bounded value
+        // proves nothing, so the other forms are tried
+        let forms = [
+            self.0.clone(),
+            self.0.simplify(),
+            self.0.factor(),
+            self.simplify_trig(TrigForm::Shortest).0,
+        ];
+        let mut values = Vec::new();
+        for (point, side) in approaches {
+            let approach = Approach { point, side };
+            let value = forms
+                .iter()
+                .filter_map(|func| approach.limit(&approach.unfold_abs(func), 0))
+                .find(|value| *value != Value::Bounded);
+            match value {
+                Some(value) => values.push(value),
+                None => return Err(LimitError::Undetermined(self.to_string())),
+            }
+        }
+        if values.windows(2).any(|pair| pair[0] != pair[1]) {
+            return Ok(Limit::DoesNotExist);
+        }
+
+        Ok(match values.swap_remove(0) {
+            Value::Finite(val) => Limit::Finite(F1D(val)),
+            Value::PosInf => Limit::PosInfinity,
+            Value::NegInf => Limit::NegInfinity,
+            Value::Oscillating | Value::Undefined => Limit::DoesNotExist,
+            Value::Bounded => unreachable!("Bounded values are undetermined"),
+        })
+    }
+}
+
+#[test]
+fn test_limit() {
+    use std::str::FromStr;
+
+    let zero = || Point::Finite(Rational::zero());
+    let value = |func: &str| Ok(Limit::Finite(F1D::from_str(func).unwrap().simplify()));
+    let cases = [
+        ("sin(x)/x", zero(), Direction::Both, value("1")),
+        ("(1-cos(x))/x^2", zero(), Direction::Both, value("1/2")),
+        (
+            "(e^x-1-x)/x^3",
+            zero(),
+            Direction::Right,
+            Ok(Limit::PosInfinity),
+        ),
+        (
+            "(e^x-1-x)/x^3",
+            zero(),
+            Direction::Left,
+            Ok(Limit::NegInfinity),
+        ),
+        (
+            "(e^x-1-x)/x^3",
+            zero(),
+            Direction::Both,
+            Ok(Limit::DoesNotExist),
+        ),
+        ("1/x^2", zero(), Direction::Both, Ok(Limit::PosInfinity)),
+        ("xln(x)", zero(), Direction::Right, value("0")),
+        ("ln(x)", zero(), Direction::Left, Ok(Limit::DoesNotExist)),
+        ("x^x", zero(), Direction::Right, value("1")),
+        ("xsin(1/x)", zero(), Direction::Both, value("0")),
+        (
+            "sin(1/x)",
+            zero(),
+            Direction::Right,
+            Ok(Limit::DoesNotExist),
+        ),
+        ("abs(x)/x", zero(), Direction::Both, Ok(Limit::DoesNotExist)),
+        (
+            "(x^2-1)/(x-1)",
+            Point::Finite(Rational::new_from_int(1)),
+            Direction::Both,
+            value("2"),
+        ),
+        ("(1+1/x)^x", Point::PosInfinity, Direction::Both, value("e")),
+        ("x^3/e^x", Point::PosInfinity, Direction::Both, value("0")),
+        (
+            "ln(x)/x^(1/2)",
+            Point::PosInfinity,
+            Direction::Both,
+            value("0"),
+        ),
+        (
+            "x-ln(x)",
+            Point::PosInfinity,
+            Direction::Both,
+            Ok(Limit::PosInfinity),
+        ),
+        (
+            "(2x^2+1)/(3-x^2)",
+            Point::NegInfinity,
+            Direction::Both,
+            value("-2"),
+        ),
+        (
+            "atan(x)",
+            Point::NegInfinity,
+            Direction::Both,
+            value("-pi/2"),
+        ),
+        ("sin(x)/x", Point::PosInfinity, Direction::Both, value("0")),
+        (
+            "cos(x)",
+            Point::PosInfinity,
+            Direction::Both,
+            Ok(Limit::DoesNotExist),
+        ),
+        (
+            "x^3-x^5",
+            Point::NegInfinity,
+            Direction::Both,
+            Ok(Limit::PosInfinity),
+        ),
+        (
+            "e^(-x)",
+            Point::NegInfinity,
+            Direction::Both,
+            Ok(Limit::PosInfinity),
+        ),
+        (
+            "x^2+3",
+            Point::Finite(Rational::new(1, 2)),
+            Direction::Both,
+            value("13/4"),
+        ),
+        (
+            "sin(x)^2+cos(x)^2",
+            Point::PosInfinity,
+            Direction::Both,
+            value("1"),
+        ),
+        (
+            "2sin(x)+3",
+            Point::PosInfinity,
+            Direction::Both,
+            Ok(Limit::DoesNotExist),
+        ),
+        (
+            "atan(sin(x))",
+            Point::NegInfinity,
+            Direction::Both,
+            Ok(Limit::DoesNotExist),
+        ),
+        (
+            "sin(x)+sin(2x)",
+            Point::PosInfinity,
+            Direction::Both,
+            Err(LimitError::Undetermined("sin(x)+sin(2x)".to_string())),
+        ),
+        (
+            "sin(x)cos(x)",
+            Point::PosInfinity,
+            Direction::Both,
+            Ok(Limit::DoesNotExist),
+        ),
+        (
+            "x+sin(x)",
+            Point::PosInfinity,
+            Direction::Both,
+            Ok(Limit::PosInfinity),
+        ),
+    ];
+
+    for (func, point, direction, expected) in cases {
+        let func = F1D::from_str(func).unwrap();
+        assert_eq!(func.limit(point, direction), expected, "{func}");
+    }
+}
@@ -0,0 +1,314 @@
+use crate::algebra::rational::Rational;
+use crate::simplify::binary;
+use crate::{Function, FunctionType, Operation, F1D, F2D, F3D};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Rules `simplify_logexp` is allowed to use
+pub enum LogDomain {
+    /// Only rules that hold wherever the function is defined: ln(ab) is split on positive
+    /// factors only and ln(x^2) becomes 2ln(abs(x))
+    Real,
+    /// Arguments of logarithms and bases of powers are assumed positive, so ln(ab) = ln(a)+ln(b)
+    /// and ln(a^b) = bln(a) always
+    Positive,
+}
+
+/// Passes of rewriting and simplifying before giving up on a fixed point
+const MAX_PASSES: usize = 4;
+
+fn ln(argument: Function) -> Function {
+    Function::Special {
+        kind: FunctionType::Ln,
+        argument: Box::new(argument),
+    }
+}
+
+/// Positive for every value of the variables
+fn is_positive(func: &Function) -> bool {
+    match func {
+        Function::E | Function::PI => true,
+        Function::Rational(val) => val.num() > 0,
+        Function::Binary {
+            operation: Operation::Mul | Operation::Div,
+            terms,
+        } => is_positive(&terms.0) && is_positive(&terms.1),
+        Function::Binary {
+            operation: Operation::Pow,
+            terms,
+        } => is_positive(&terms.0),
+        _ => false,
+    }
+}
+
+/// ln(argument), with the argument already rewritten
+fn log_of(argument: Function, domain: LogDomain) -> Function {
+    let split = |a: &Function, b: &Function| {
+        domain == LogDomain::Positive || is_positive(a) || is_positive(b)
+    };
+
+    match &argument {
+        Function::E => Function::Rational(Rational::new_from_int(1)),
+        Function::Binary { operation, terms } => {
+            let (a, b) = (&*terms.0, &*terms.1);
+            match operation {
+                Operation::Pow if *a == Function::E => b.clone(),
+                Operation::Mul if split(a, b) => binary(
+                    Operation::Add,
+                    log_of(a.clone(), domain),
+                    log_of(b.clone(), domain),
+                ),
+                Operation::Div if split(a, b) => binary(
+                    Operation::Sub,
+                    log_of(a.clone(), domain),
+                    log_of(b.clone(), domain),
+                ),
+                Operation::Pow => match b {
+                    // x^2 is defined for negative x, its log isn't 2ln(x)
+                    Function::Rational(exp)
+                        if domain == LogDomain::Real
+                            && exp.is_integer()
+                            && exp.num() % 2 == 0
+                            && !is_positive(a) =>
+                    {
+                        binary(
+                            Operation::Mul,
+                            b.clone(),
+                            ln(Function::Special {
+                                kind: FunctionType::Abs,
+                                argument: Box::new(a.clone()),
+                            }),
+                        )
+                    }
+                    // Other rational powers of negative numbers aren't evaluated
+                    Function::Rational(_) => {
+                        binary(Operation::Mul, b.clone(), log_of(a.clone(), domain))
+                    }
+                    _ if domain == LogDomain::Positive || is_positive(a) => {
+                        binary(Operation::Mul, b.clone(), log_of(a.clone(), domain))
+                    }
+                    _ => ln(argument),
+                },
+                _ => ln(argument),
+            }
+        }
+        _ => ln(argument),
+    }
+}
+
+/// Adds the terms of a sum to the list, with their signs
+fn addends(func: &Function, positive: bool, terms: &mut Vec<(bool, Function)>) {
+    match func {
+        Function::Binary {
+            operation: Operation::Add,
+            terms: (a, b),
+        } => {
+            addends(a, positive, terms);
+            addends(b, positive, terms);
+        }
+        Function::Binary {
+            operation: Operation::Sub,
+            terms: (a, b),
+        } => {
+            addends(a, positive, terms);
+            addends(b, !positive, terms);
+        }
+        _ => terms.push((positive, func.clone())),
+    }
+}
+
+/// The u^k a term k*ln(u) of an exponent of e stands for
+fn log_power(term: &Function) -> Option<(Function, Rational)> {
+    match term {
+        Function::Special {
+            kind: FunctionType::Ln,
+            argument,
+        } => Some((*argument.clone(), Rational::new_from_int(1))),
+        Function::Binary {
+            operation: Operation::Mul,
+            terms,
+        } => match (&*terms.0, &*terms.1) {
+            (
+                Function::Rational(k),
+                Function::Special {
+                    kind: FunctionType::Ln,
+                    argument,
+                },
+            )
+            | (
+                Function::Special {
+                    kind: FunctionType::Ln,
+                    argument,
+                },
+                Function::Rational(k),
+            ) => Some((*argument.clone(), k.clone())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// e^exponent, with the exponent already rewritten. Logarithms in the exponent become factors,
+/// e^(ln(u)) = u holds wherever the left side is defined
+fn exp_of(exponent: Function) -> Function {
+    let mut terms = Vec::new();
+    addends(&exponent, true, &mut terms);
+
+    let mut factors = Vec::new();
+    let mut rest: Option<Function> = None;
+    for (positive, term) in terms {
+        if let Some((base, k)) = log_power(&term) {
+            let k = if positive {
+                k
+            } else {
+                Rational::new_from_int(-1) * k
+            };
+            factors.push(binary(Operation::Pow, base, Function::Rational(k)));
+            continue;
+        }
+        rest = Some(match (rest, positive) {
+            (None, true) => term,
+            (None, false) => binary(
+                Operation::Mul,
+                Function::Rational(Rational::new_from_int(-1)),
+                term,
+            ),
+            (Some(acc), true) => binary(Operation::Add, acc, term),
+            (Some(acc), false) => binary(Operation::Sub, acc, term),
+        });
+    }
+
+    if let Some(rest) = rest {
+        factors.push(binary(Operation::Pow, Function::E, rest));
+    }
+    factors
+        .into_iter()
+        .reduce(|acc, factor| binary(Operation::Mul, acc, factor))
+        .unwrap_or(Function::Rational(Rational::new_from_int(1)))
+}
+
+fn rewrite(func: &Function, domain: LogDomain) -> Function {
+    match func {
+        Function::Binary { operation, terms } => {
+            let (a, b) = (rewrite(&terms.0, domain), rewrite(&terms.1, domain));
+            match (operation, &a) {
+                (Operation::Pow, Function::E) => exp_of(b),
+                _ => binary(operation.clone(), a, b),
+            }
+        }
+        Function::Special { kind, argument } => {
+            let argument = rewrite(argument, domain);
+            match kind {
+                FunctionType::Ln => log_of(argument, domain),
+                _ => Function::Special {
+                    kind: kind.clone(),
+                    argument: Box::new(argument),
+                },
+            }
+        }
+        _ => func.clone(),
+    }
+}
+
+/// Rewrites and simplifies until nothing changes, simplifying also joins e^a*e^b into e^(a+b)
+fn simplify_logexp(func: &Function, domain: LogDomain) -> Function {
+    // Rewritten before simplifying, which could distribute the product inside a logarithm
+    let mut current = rewrite(func, domain).simplify();
+    for _ in 1..MAX_PASSES {
+        let next = rewrite(&current, domain).simplify();
+        if next == current {
+            break;
+        }
+        current = next;
+    }
+    current
+}
+
+impl F1D {
+    /// Simplifies with the rules of logarithms and exponentials
+    /// ```
+    /// use ruut_functions::{F1D, logexp::LogDomain};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("ln(e^(2x))+e^(x+ln(x))").unwrap();
+    /// assert_eq!(func.simplify_logexp(LogDomain::Real).to_string(), "2x+xe^x");
+    ///
+    /// let func = F1D::from_str("ln(x^2)").unwrap();
+    /// assert_eq!(func.simplify_logexp(LogDomain::Real).to_string(), "2ln(abs(x))");
+    /// assert_eq!(func.simplify_logexp(LogDomain::Positive).to_string(), "2ln(x)");
+    /// ```
+    pub fn simplify_logexp(&self, domain: LogDomain) -> Self {
+        F1D(simplify_logexp(&self.0, domain))
+    }
+}
+impl F2D {
+    /// Simplifies with the rules of logarithms and exponentials
+    /// ```
+    /// use ruut_functions::{F2D, logexp::LogDomain};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("ln(xy)-ln(x)").unwrap();
+    /// assert_eq!(func.simplify_logexp(LogDomain::Positive).to_string(), "ln(y)");
+    /// ```
+    pub fn simplify_logexp(&self, domain: LogDomain) -> Self {
+        F2D(simplify_logexp(&self.0, domain))
+    }
+}
+impl F3D {
+    /// Simplifies with the rules of logarithms and exponentials
+    /// ```
+    /// use ruut_functions::{F3D, logexp::LogDomain};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("e^xe^ye^(z-x)").unwrap();
+    /// assert_eq!(func.simplify_logexp(LogDomain::Real).to_string(), "e^(y+z)");
+    /// ```
+    pub fn simplify_logexp(&self, domain: LogDomain) -> Self {
+        F3D(simplify_logexp(&self.0, domain))
+    }
+}
+
+#[test]
+fn test_logexp() {
+    use std::str::FromStr;
+
+    let cases = [
+        ("ln(e^x)", "x", "x"),
+        ("ln(2x)", "ln(x)+ln(2)", "ln(x)+ln(2)"),
+        ("ln(x^3)", "3ln(x)", "3ln(x)"),
+        ("ln(x^2)", "2ln(abs(x))", "2ln(x)"),
+        ("ln(x^(1/2))", "ln(x)/2", "ln(x)/2"),
+        ("ln(x(x+1))", "ln(x(x+1))", "ln(x+1)+ln(x)"),
+        ("ln(e^x/x)", "x-ln(x)", "x-ln(x)"),
+        ("ln(2^x)", "xln(2)", "xln(2)"),
+        ("ln(x^x)", "ln(x^x)", "xln(x)"),
+        ("e^(x+2ln(x))", "x^2e^x", "x^2e^x"),
+        ("e^xe^(-x)", "1", "1"),
+        ("e^(x-ln(x))", "e^x/x", "e^x/x"),
+    ];
+    for (func, real, positive) in cases {
+        let func = F1D::from_str(func).unwrap();
+        for (domain, expected) in [(LogDomain::Real, real), (LogDomain::Positive, positive)] {
+            let expected = F1D::from_str(expected).unwrap().simplify();
+            assert_eq!(func.simplify_logexp(domain), expected, "{func}");
+        }
+    }
+
+    // The rewritten functions agree wherever the original is defined
+    for func in [
+        "ln(x^2(x-3)^2)",
+        "ln(x^4)+e^(ln(x)+x)",
+        "ln(e^(x^2)(x+1)^(1/2))",
+    ] {
+        let func = F1D::from_str(func).unwrap();
+        let rewritten = func.simplify_logexp(LogDomain::Real);
+        for x in [-2.5, -0.4, 0.7, 1.3, 4.2]
+            .into_iter()
+            .filter(|x| func.eval(*x).is_finite())
+        {
+            assert!(
+                (rewritten.eval(x) - func.eval(x)).abs() < 1e-9,
+                "{func} {rewritten}"
+            );
+        }
+    }
+}
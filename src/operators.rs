@@ -138,8 +138,8 @@ impl Sub for Function {
                 return self;
             }
 
-            if let Function::Rational(self_val) = &rhs {
-                return Function::Rational(val.clone() - self_val.clone());
+            if let Function::Rational(self_val) = &self {
+                return Function::Rational(self_val.clone() - val.clone());
             }
         }
 
@@ -194,7 +194,7 @@ impl Mul for Function {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        if &self == &rhs {
+        if self == rhs {
             return self.powr(Rational::new_from_int(2));
         }
 
@@ -281,7 +281,7 @@ impl Mul for Function {
         }
 
         if let Function::Binary {
-            operation: _,
+            operation: Operation::Mul,
             terms,
         } = &self
         {
@@ -476,3 +476,19 @@ impl Function {
         }
     }
 }
+
+#[test]
+fn test_operators() {
+    let two = || Function::Rational(Rational::new_from_int(2));
+
+    let func = F2D(Function::X - two());
+    assert_eq!(func.eval(5., 0.), 3.);
+    let func = F2D(two() - Function::X);
+    assert_eq!(func.eval(5., 0.), -3.);
+
+    // Only products move the coefficient out
+    let func = F2D((two() + Function::X) * Function::Y);
+    assert_eq!(func.eval(1., 3.), 9.);
+    let func = F2D((two() * Function::X) * Function::Y);
+    assert_eq!(func.eval(1., 3.), 6.);
+}
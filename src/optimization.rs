@@ -0,0 +1,278 @@
+use crate::{Function, Matrix, F2D, F3D};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Kind of a critical point, from the eigenvalues of the Hessian
+pub enum CriticalKind {
+    /// All the eigenvalues are positive
+    Minimum,
+    /// All the eigenvalues are negative
+    Maximum,
+    /// There are both positive and negative eigenvalues
+    Saddle,
+    /// The determinant vanishes and the remaining eigenvalues have the same sign, second
+    /// derivatives can't tell
+    Degenerate,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Point where the gradient vanishes, N is the number of variables
+pub struct CriticalPoint<const N: usize> {
+    /// Coordinates of the point
+    pub point: [f64; N],
+    /// Value of the function at the point
+    pub value: f64,
+    /// Determinant of the Hessian at the point
+    pub determinant: f64,
+    /// Eigenvalues of the Hessian at the point, in increasing order
+    pub eigenvalues: [f64; N],
+    /// Classification of the point
+    pub kind: CriticalKind,
+}
+
+/// Newton iterations from every starting point
+const MAX_ITERATIONS: usize = 100;
+/// Gradients smaller than this are zero
+const GRADIENT_TOLERANCE: f64 = 1e-8;
+/// Eigenvalues smaller than this, relative to the largest one, are zero
+const EIGENVALUE_TOLERANCE: f64 = 1e-8;
+
+/// Gradient and Hessian of a function of N variables
+struct Derivatives<const N: usize> {
+    func: Function,
+    gradient: Vec<Function>,
+    hessian: Vec<Function>,
+}
+
+impl<const N: usize> Derivatives<N> {
+    fn new(func: &Function) -> Self {
+        let gradient: Vec<_> = (0..N).map(|i| func.derivative_by(i).simplify()).collect();
+        let hessian = gradient
+            .iter()
+            .flat_map(|partial| (0..N).map(move |j| partial.derivative_by(j).simplify()))
+            .collect();
+
+        Derivatives {
+            func: func.clone(),
+            gradient,
+            hessian,
+        }
+    }
+
+    fn eval(funcs: &[Function], point: &[f64; N]) -> Vec<f64> {
+        let mut inputs = [0.; 3];
+        inputs[..N].copy_from_slice(point);
+        funcs.iter().map(|func| func.evaluate(&inputs)).collect()
+    }
+
+    /// Zero of the gradient reached with Newton's method, if any
+    fn newton(&self, mut point: [f64; N]) -> Option<[f64; N]> {
+        for _ in 0..MAX_ITERATIONS {
+            let gradient = Self::eval(&self.gradient, &point);
+            let hessian = Self::eval(&self.hessian, &point);
+            let Some(step) = solve::<N>(hessian, gradient) else {
+                break;
+            };
+
+            let mut converged = true;
+            for (coord, delta) in point.iter_mut().zip(step) {
+                *coord -= delta;
+                converged &= delta.abs() <= 1e-14 * coord.abs().max(1.);
+            }
+            if converged {
+                break;
+            }
+        }
+
+        let gradient = Self::eval(&self.gradient, &point);
+        let norm = gradient.iter().map(|val| val * val).sum::<f64>().sqrt();
+        (norm < GRADIENT_TOLERANCE).then_some(point)
+    }
+
+    fn classify(&self, point: [f64; N]) -> CriticalPoint<N> {
+        let hessian = Matrix::new(Self::eval(&self.hessian, &point), N, N);
+        let eigenvalues = hessian.symmetric_eigenvalues();
+        let scale = eigenvalues.iter().fold(1f64, |acc, val| acc.max(val.abs()));
+        let tolerance = EIGENVALUE_TOLERANCE * scale;
+
+        let positive = eigenvalues.iter().any(|val| *val > tolerance);
+        let negative = eigenvalues.iter().any(|val| *val < -tolerance);
+        let singular = eigenvalues.iter().any(|val| val.abs() <= tolerance);
+        let kind = match (positive, negative, singular) {
+            (true, true, _) => CriticalKind::Saddle,
+            (_, _, true) => CriticalKind::Degenerate,
+            (true, false, false) => CriticalKind::Minimum,
+            _ => CriticalKind::Maximum,
+        };
+
+        CriticalPoint {
+            point,
+            value: Self::eval(std::slice::from_ref(&self.func), &point)[0],
+            determinant: hessian.determinant(),
+            eigenvalues: eigenvalues.try_into().expect("one eigenvalue per variable"),
+            kind,
+        }
+    }
+}
+
+/// Solves hessian * x = gradient with Gaussian elimination, None if the matrix is singular
+fn solve<const N: usize>(mut mat: Vec<f64>, mut rhs: Vec<f64>) -> Option<Vec<f64>> {
+    for col in 0..N {
+        let pivot =
+            (col..N).max_by(|a, b| mat[a * N + col].abs().total_cmp(&mat[b * N + col].abs()))?;
+        if mat[pivot * N + col] == 0. || !mat[pivot * N + col].is_finite() {
+            return None;
+        }
+        for k in 0..N {
+            mat.swap(col * N + k, pivot * N + k);
+        }
+        rhs.swap(col, pivot);
+
+        for row in (col + 1)..N {
+            let factor = mat[row * N + col] / mat[col * N + col];
+            for k in col..N {
+                mat[row * N + k] -= factor * mat[col * N + k];
+            }
+            rhs[row] -= factor * rhs[col];
+        }
+    }
+
+    let mut solution = vec![0.; N];
+    for row in (0..N).rev() {
+        let known: f64 = ((row + 1)..N).map(|k| mat[row * N + k] * solution[k]).sum();
+        solution[row] = (rhs[row] - known) / mat[row * N + row];
+    }
+    solution
+        .iter()
+        .all(|val| val.is_finite())
+        .then_some(solution)
+}
+
+/// Newton's method from a grid of starting points, keeping the distinct zeros in the box
+fn critical_points<const N: usize>(
+    func: &Function,
+    bounds: [(f64, f64); N],
+    samples: usize,
+) -> Vec<CriticalPoint<N>> {
+    let derivatives = Derivatives::<N>::new(func);
+    let mut found: Vec<[f64; N]> = Vec::new();
+    let close = |a: &[f64; N], b: &[f64; N]| {
+        a.iter()
+            .zip(b)
+            .zip(&bounds)
+            .all(|((a, b), (lo, hi))| (a - b).abs() <= 1e-6 * (hi - lo).max(1.))
+    };
+
+    for index in 0..samples.pow(N as u32) {
+        // Centers of the cells of the grid
+        let mut start = [0.; N];
+        let mut rest = index;
+        for (coord, (lo, hi)) in start.iter_mut().zip(&bounds) {
+            *coord = lo + (hi - lo) * ((rest % samples) as f64 + 0.5) / samples as f64;
+            rest /= samples;
+        }
+
+        let Some(point) = derivatives.newton(start) else {
+            continue;
+        };
+        let inside = point
+            .iter()
+            .zip(&bounds)
+            .all(|(coord, (lo, hi))| *coord >= *lo && *coord <= *hi);
+        if inside && !found.iter().any(|other| close(other, &point)) {
+            found.push(point);
+        }
+    }
+
+    found.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    found
+        .into_iter()
+        .map(|point| derivatives.classify(point))
+        .collect()
+}
+
+impl F2D {
+    /// Finds the points of the box [x0, x1] x [y0, y1] where the gradient vanishes, solving
+    /// numerically from a grid of starting points, and classifies them with the Hessian
+    /// ```
+    /// use ruut_functions::{F2D, optimization::CriticalKind};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("x^3-3x+y^2").unwrap();
+    /// let points = func.critical_points([(-2., 2.), (-2., 2.)]);
+    ///
+    /// assert_eq!(points.len(), 2);
+    /// assert_eq!(points[0].kind, CriticalKind::Saddle);
+    /// assert!((points[1].point[0] - 1.).abs() < 1e-9);
+    /// assert_eq!(points[1].kind, CriticalKind::Minimum);
+    /// ```
+    pub fn critical_points(&self, bounds: [(f64, f64); 2]) -> Vec<CriticalPoint<2>> {
+        critical_points(&self.0, bounds, 12)
+    }
+}
+
+impl F3D {
+    /// Finds the points of the box [x0, x1] x [y0, y1] x [z0, z1] where the gradient vanishes,
+    /// solving numerically from a grid of starting points, and classifies them with the Hessian
+    /// ```
+    /// use ruut_functions::{F3D, optimization::CriticalKind};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("x^2+y^2-z^2+2z").unwrap();
+    /// let points = func.critical_points([(-1., 1.), (-1., 1.), (0., 2.)]);
+    ///
+    /// assert_eq!(points.len(), 1);
+    /// assert_eq!(points[0].kind, CriticalKind::Saddle);
+    /// assert_eq!(points[0].eigenvalues, [-2., 2., 2.]);
+    /// ```
+    pub fn critical_points(&self, bounds: [(f64, f64); 3]) -> Vec<CriticalPoint<3>> {
+        critical_points(&self.0, bounds, 6)
+    }
+}
+
+#[test]
+fn test_critical_points() {
+    use std::str::FromStr;
+
+    let func = F2D::from_str("x^3-3x+y^3-3y").unwrap();
+    let points = func.critical_points([(-3., 3.), (-3., 3.)]);
+    let kinds: Vec<_> = points.iter().map(|point| point.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            CriticalKind::Maximum,
+            CriticalKind::Saddle,
+            CriticalKind::Saddle,
+            CriticalKind::Minimum
+        ]
+    );
+    for (point, expected) in points
+        .iter()
+        .zip([[-1., -1.], [-1., 1.], [1., -1.], [1., 1.]])
+    {
+        assert!((point.point[0] - expected[0]).abs() < 1e-9);
+        assert!((point.point[1] - expected[1]).abs() < 1e-9);
+    }
+    assert!((points[0].value - 4.).abs() < 1e-9);
+    assert!((points[1].determinant + 36.).abs() < 1e-6);
+
+    // Only the ones in the box
+    let points = func.critical_points([(0., 3.), (0., 3.)]);
+    assert_eq!(points.len(), 1);
+
+    // The second derivative test fails at the origin
+    let func = F2D::from_str("x^4+y^4").unwrap();
+    let points = func.critical_points([(-1., 1.), (-1., 1.)]);
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].kind, CriticalKind::Degenerate);
+
+    let func = F2D::from_str("sin(x)sin(y)").unwrap();
+    let points = func.critical_points([(0.5, 3.), (0.5, 3.)]);
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].kind, CriticalKind::Maximum);
+
+    let func = F3D::from_str("(x-1)^2+(y+2)^2+z^2+xy").unwrap();
+    let points = func.critical_points([(-5., 5.), (-5., 5.), (-5., 5.)]);
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].kind, CriticalKind::Minimum);
+    assert!(points[0].determinant > 0.);
+}
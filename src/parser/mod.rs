@@ -1,3 +1,4 @@
+#[allow(clippy::module_inception)]
 mod parser;
 pub(crate) mod splitter;
 pub(crate) use parser::parse;
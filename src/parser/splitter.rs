@@ -2,7 +2,7 @@ use crate::Operation;
 use std::error::Error;
 use std::fmt::Display;
 
-pub(crate) fn split(input: &str) -> Result<Split, ParsingError> {
+pub(crate) fn split(input: &str) -> Result<Split<'_>, ParsingError> {
     if input.is_empty() {
         return Err(ParsingError::EmptyInput);
     }
@@ -165,31 +165,33 @@ impl Display for ParsingError {
 }
 impl Error for ParsingError {}
 
-#[test]
-fn test_tokenzier() {
-    impl<'a> Split<'a> {
-        fn build(
-            first_operand: &'a str,
-            second_operand: Option<&'a str>,
-            operation: char,
-        ) -> Split<'a> {
-            let operator = match operation {
-                '+' => Operation::Add,
-                '-' => Operation::Sub,
-                '*' => Operation::Mul,
-                '/' => Operation::Div,
-                '^' => Operation::Pow,
-                '(' => Operation::Comp,
-                _ => panic!("Called update with invalid operation"),
-            };
+#[cfg(test)]
+impl<'a> Split<'a> {
+    fn build(
+        first_operand: &'a str,
+        second_operand: Option<&'a str>,
+        operation: char,
+    ) -> Split<'a> {
+        let operator = match operation {
+            '+' => Operation::Add,
+            '-' => Operation::Sub,
+            '*' => Operation::Mul,
+            '/' => Operation::Div,
+            '^' => Operation::Pow,
+            '(' => Operation::Comp,
+            _ => panic!("Called update with invalid operation"),
+        };
 
-            Split {
-                first_operand,
-                second_operand,
-                operator,
-            }
+        Split {
+            first_operand,
+            second_operand,
+            operator,
         }
     }
+}
+
+#[test]
+fn test_tokenzier() {
     assert_eq!(split("x^0.5").unwrap(), Split::build("x", Some("0.5"), '^'));
     assert_eq!(split("3x").unwrap(), Split::build("3", Some("x"), '*'));
     assert_eq!(
@@ -0,0 +1,515 @@
+use crate::{Function, FunctionType, Operation, F1D, F2D, F3D};
+pub use dashu_float::DBig;
+use std::error::Error;
+use std::fmt::Display;
+
+/// Extra digits carried during evaluation and dropped when rounding the result
+const GUARD_DIGITS: usize = 10;
+
+#[derive(Debug, PartialEq)]
+/// Errors that can occur while evaluating with arbitrary precision
+pub enum PrecisionError {
+    /// A denominator evaluated to zero
+    DivisionByZero,
+    /// The argument of the given node is outside of its domain
+    OutOfDomain(String),
+}
+
+impl Display for PrecisionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DivisionByZero => write!(f, "Division by zero"),
+            Self::OutOfDomain(node) => write!(f, "Argument of {node} is outside of its domain"),
+        }
+    }
+}
+impl Error for PrecisionError {}
+
+struct Evaluator {
+    precision: usize,
+    inputs: [DBig; 3],
+    pi: DBig,
+}
+
+impl Evaluator {
+    fn new(inputs: [&DBig; 3], digits: usize) -> Self {
+        let precision = digits + GUARD_DIGITS;
+        let inputs = inputs.map(|input| input.clone().with_precision(precision).value());
+        Evaluator {
+            precision,
+            inputs,
+            pi: pi(precision),
+        }
+    }
+
+    fn int(&self, num: i32) -> DBig {
+        DBig::from(num).with_precision(self.precision).value()
+    }
+
+    fn evaluate(&self, func: &Function) -> Result<DBig, PrecisionError> {
+        match func {
+            Function::X => Ok(self.inputs[0].clone()),
+            Function::Y => Ok(self.inputs[1].clone()),
+            Function::Z => Ok(self.inputs[2].clone()),
+            Function::E => Ok(self.int(1).exp()),
+            Function::PI => Ok(self.pi.clone()),
+            Function::Rational(val) => Ok(self.int(val.num()) / self.int(val.den())),
+            Function::Binary { operation, terms } => {
+                if let Operation::Pow = operation {
+                    return self.eval_pow(func, &terms.0, &terms.1);
+                }
+
+                let left = self.evaluate(&terms.0)?;
+                let right = self.evaluate(&terms.1)?;
+                match operation {
+                    Operation::Add => Ok(left + right),
+                    Operation::Sub => Ok(left - right),
+                    Operation::Mul => Ok(left * right),
+                    Operation::Div => {
+                        if right.repr().is_zero() {
+                            return Err(PrecisionError::DivisionByZero);
+                        }
+                        Ok(left / right)
+                    }
+                    Operation::Pow | Operation::Comp => panic!("Something went wrong"),
+                }
+            }
+            Function::Special { kind, argument } => {
+                let arg = self.evaluate(argument)?;
+                self.eval_special(func, kind, arg)
+            }
+        }
+    }
+
+    fn eval_pow(
+        &self,
+        func: &Function,
+        base: &Function,
+        exponent: &Function,
+    ) -> Result<DBig, PrecisionError> {
+        if let Function::E = base {
+            return Ok(self.evaluate(exponent)?.exp());
+        }
+
+        let base = self.evaluate(base)?;
+        let out_of_domain = || PrecisionError::OutOfDomain(func.to_string());
+
+        if let Function::Rational(exp) = exponent {
+            if base.repr().is_zero() {
+                return match exp.num() {
+                    0 => Ok(self.int(1)),
+                    n if n > 0 => Ok(self.int(0)),
+                    _ => Err(PrecisionError::DivisionByZero),
+                };
+            }
+            if exp.is_integer() {
+                return Ok(base.powi(exp.num().into()));
+            }
+            if base < DBig::ZERO {
+                // Odd roots of negative numbers are real
+                if exp.den() % 2 == 0 {
+                    return Err(out_of_domain());
+                }
+                let abs = (-base).powf(&(self.int(exp.num()) / self.int(exp.den())));
+                return Ok(if exp.num() % 2 == 0 { abs } else { -abs });
+            }
+            return Ok(base.powf(&(self.int(exp.num()) / self.int(exp.den()))));
+        }
+
+        let exp = self.evaluate(exponent)?;
+        if base.repr().is_zero() {
+            return if exp > DBig::ZERO {
+                Ok(self.int(0))
+            } else {
+                Err(PrecisionError::DivisionByZero)
+            };
+        }
+        if base < DBig::ZERO {
+            if !exp.repr().is_int() {
+                return Err(out_of_domain());
+            }
+            return Ok(base.powi(exp.to_int().value()));
+        }
+        Ok(base.powf(&exp))
+    }
+
+    fn eval_special(
+        &self,
+        func: &Function,
+        kind: &FunctionType,
+        arg: DBig,
+    ) -> Result<DBig, PrecisionError> {
+        let out_of_domain = || PrecisionError::OutOfDomain(func.to_string());
+        let one = self.int(1);
+
+        match kind {
+            FunctionType::Ln => {
+                if arg <= DBig::ZERO {
+                    return Err(out_of_domain());
+                }
+                Ok(arg.ln())
+            }
+            FunctionType::Sin => Ok(self.sin(&arg)),
+            FunctionType::Cos => Ok(self.cos(&arg)),
+            FunctionType::Tan => self.div(self.sin(&arg), self.cos(&arg)),
+            FunctionType::Cot => self.div(self.cos(&arg), self.sin(&arg)),
+            FunctionType::Sec => self.div(one, self.cos(&arg)),
+            FunctionType::Csc => self.div(one, self.sin(&arg)),
+            FunctionType::ASin => self.asin(arg).ok_or_else(out_of_domain),
+            FunctionType::ACos => {
+                let asin = self.asin(arg).ok_or_else(out_of_domain)?;
+                Ok(self.pi.clone() / self.int(2) - asin)
+            }
+            FunctionType::ATan => Ok(self.atan(arg)),
+            FunctionType::Sinh => Ok(self.sinh(&arg)),
+            FunctionType::Cosh => Ok(self.cosh(&arg)),
+            FunctionType::Tanh => Ok(self.tanh(&arg)),
+            FunctionType::Coth => self.div(one, self.tanh(&arg)),
+            FunctionType::Sech => Ok(one / self.cosh(&arg)),
+            FunctionType::Csch => self.div(one, self.sinh(&arg)),
+            FunctionType::ASinh => {
+                if self.is_small(&arg) {
+                    // x - x^3/6 + 3x^5/40 - ...
+                    let square = arg.clone() * arg.clone();
+                    return Ok(series(arg, |term, k| {
+                        -term * square.clone() * self.int((2 * k - 1) * (2 * k - 1))
+                            / self.int((2 * k) * (2 * k + 1))
+                    }));
+                }
+                let abs = if arg < DBig::ZERO {
+                    -arg.clone()
+                } else {
+                    arg.clone()
+                };
+                let res = (abs.clone() + self.sqrt(abs.clone() * abs + one)).ln();
+                Ok(if arg < DBig::ZERO { -res } else { res })
+            }
+            FunctionType::ACosh => {
+                if arg < one {
+                    return Err(out_of_domain());
+                }
+                Ok((arg.clone() + self.sqrt(arg.clone() * arg - one)).ln())
+            }
+            FunctionType::ATanh => {
+                if arg <= -one.clone() || arg >= one {
+                    return Err(out_of_domain());
+                }
+                if self.is_small(&arg) {
+                    // x + x^3/3 + x^5/5 + ...
+                    let square = arg.clone() * arg.clone();
+                    return Ok(series(arg, |term, k| {
+                        term * square.clone() * self.int(2 * k - 1) / self.int(2 * k + 1)
+                    }));
+                }
+                Ok(((one.clone() + arg.clone()) / (one - arg)).ln() / self.int(2))
+            }
+            FunctionType::Abs => Ok(if arg < DBig::ZERO { -arg } else { arg }),
+        }
+    }
+
+    fn div(&self, num: DBig, den: DBig) -> Result<DBig, PrecisionError> {
+        if den.repr().is_zero() {
+            return Err(PrecisionError::DivisionByZero);
+        }
+        Ok(num / den)
+    }
+
+    fn sqrt(&self, arg: DBig) -> DBig {
+        arg.context().sqrt(arg.repr()).value()
+    }
+
+    /// Below 1/2 in absolute value the formulas with exponentials and logarithms cancel out
+    /// most of the digits, so the Taylor series is used instead
+    fn is_small(&self, arg: &DBig) -> bool {
+        let abs = if *arg < DBig::ZERO {
+            -arg.clone()
+        } else {
+            arg.clone()
+        };
+        abs * self.int(2) < self.int(1)
+    }
+
+    fn sinh(&self, arg: &DBig) -> DBig {
+        if self.is_small(arg) {
+            let square = arg.clone() * arg.clone();
+            return series(arg.clone(), |term, k| {
+                term * square.clone() / self.int((2 * k) * (2 * k + 1))
+            });
+        }
+        (arg.exp() - (-arg.clone()).exp()) / self.int(2)
+    }
+
+    fn cosh(&self, arg: &DBig) -> DBig {
+        (arg.exp() + (-arg.clone()).exp()) / self.int(2)
+    }
+
+    fn tanh(&self, arg: &DBig) -> DBig {
+        self.sinh(arg) / self.cosh(arg)
+    }
+
+    /// Reduces the argument to [-pi, pi], the extra precision needed for large arguments
+    /// is recovered by recomputing pi with as many more digits as the integer part has
+    fn reduce(&self, arg: &DBig) -> DBig {
+        let int_digits = arg.repr().digits() as isize + arg.repr().exponent();
+        if int_digits <= 0 {
+            return arg.clone();
+        }
+
+        let precision = self.precision + int_digits as usize;
+        let arg = arg.clone().with_precision(precision).value();
+        let two_pi = pi(precision) * DBig::from(2).with_precision(precision).value();
+        let turns = (arg.clone() / two_pi.clone()).to_int().value();
+
+        (arg - two_pi * DBig::from(turns))
+            .with_precision(self.precision)
+            .value()
+    }
+
+    fn sin(&self, arg: &DBig) -> DBig {
+        let arg = self.reduce(arg);
+        let square = arg.clone() * arg.clone();
+        series(arg, |term, k| {
+            -term * square.clone() / self.int((2 * k) * (2 * k + 1))
+        })
+    }
+
+    fn cos(&self, arg: &DBig) -> DBig {
+        let arg = self.reduce(arg);
+        let square = arg.clone() * arg;
+        series(self.int(1), |term, k| {
+            -term * square.clone() / self.int((2 * k - 1) * (2 * k))
+        })
+    }
+
+    fn atan(&self, arg: DBig) -> DBig {
+        if arg < DBig::ZERO {
+            return -self.atan(-arg);
+        }
+        let one = self.int(1);
+        if arg > one {
+            return self.pi.clone() / self.int(2) - self.atan(one / arg);
+        }
+
+        // atan(x) = 2atan(x/(1+sqrt(1+x^2))), applied twice to speed up the series
+        let mut arg = arg;
+        for _ in 0..2 {
+            arg = arg.clone() / (one.clone() + self.sqrt(one.clone() + arg.clone() * arg));
+        }
+        atan_series(arg) * self.int(4)
+    }
+
+    fn asin(&self, arg: DBig) -> Option<DBig> {
+        let one = self.int(1);
+        if arg > one || arg < -one.clone() {
+            return None;
+        }
+        if arg == one || arg == -one.clone() {
+            let half_pi = self.pi.clone() / self.int(2);
+            return Some(if arg < DBig::ZERO { -half_pi } else { half_pi });
+        }
+
+        Some(self.atan(arg.clone() / self.sqrt(one - arg.clone() * arg)))
+    }
+}
+
+/// Sums the series starting from `first`, `next` computes the k-th term from the previous one.
+/// Stops as soon as adding a term doesn't change the sum at the current precision
+fn series<F: Fn(DBig, i32) -> DBig>(first: DBig, next: F) -> DBig {
+    let mut sum = first.clone();
+    let mut term = first;
+    let mut k = 1;
+
+    loop {
+        term = next(term, k);
+        let new_sum = sum.clone() + term.clone();
+        if new_sum == sum {
+            return sum;
+        }
+        sum = new_sum;
+        k += 1;
+    }
+}
+
+/// Taylor series of atan, only used for small arguments
+fn atan_series(arg: DBig) -> DBig {
+    let square = arg.clone() * arg.clone();
+    let mut sum = arg.clone();
+    let mut power = arg;
+    let mut k = 1;
+
+    loop {
+        power = -power * square.clone();
+        let new_sum = sum.clone() + power.clone() / DBig::from(2 * k + 1);
+        if new_sum == sum {
+            return sum;
+        }
+        sum = new_sum;
+        k += 1;
+    }
+}
+
+/// Computes pi with Machin's formula: pi = 16atan(1/5) - 4atan(1/239)
+fn pi(precision: usize) -> DBig {
+    let int = |num: i32| DBig::from(num).with_precision(precision).value();
+
+    atan_series(int(1) / int(5)) * int(16) - atan_series(int(1) / int(239)) * int(4)
+}
+
+fn eval_precise(
+    func: &Function,
+    inputs: [&DBig; 3],
+    digits: usize,
+) -> Result<DBig, PrecisionError> {
+    let result = Evaluator::new(inputs, digits).evaluate(func)?;
+    Ok(result.with_precision(digits).value())
+}
+
+impl F1D {
+    /// Evaluate F1D at a given x with the given number of significant digits
+    /// ```
+    /// use ruut_functions::{F1D, precision::DBig};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("(1+1/x)^x").unwrap();
+    /// let x = DBig::from_str("1000000000000").unwrap();
+    ///
+    /// assert_eq!(
+    ///     func.eval_precise(&x, 20).unwrap(),
+    ///     DBig::from_str("2.7182818284576860944").unwrap()
+    /// );
+    /// ```
+    pub fn eval_precise(&self, x: &DBig, digits: usize) -> Result<DBig, PrecisionError> {
+        eval_precise(&self.0, [x, &DBig::ZERO, &DBig::ZERO], digits)
+    }
+}
+impl F2D {
+    /// Evaluate F2D at a given (x,y) with the given number of significant digits
+    /// ```
+    /// use ruut_functions::{F2D, precision::DBig};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("ysin(x)").unwrap();
+    /// let x = DBig::from_str("2").unwrap();
+    /// let y = DBig::from_str("0.5").unwrap();
+    ///
+    /// assert_eq!(
+    ///     func.eval_precise(&x, &y, 15).unwrap(),
+    ///     DBig::from_str("0.454648713412841").unwrap()
+    /// );
+    /// ```
+    pub fn eval_precise(&self, x: &DBig, y: &DBig, digits: usize) -> Result<DBig, PrecisionError> {
+        eval_precise(&self.0, [x, y, &DBig::ZERO], digits)
+    }
+}
+impl F3D {
+    /// Evaluate F3D at a given (x,y,z) with the given number of significant digits
+    /// ```
+    /// use ruut_functions::{F3D, precision::DBig};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("xln(z)/y").unwrap();
+    /// let one = DBig::from_str("1").unwrap();
+    /// let zero = DBig::from_str("0").unwrap();
+    ///
+    /// assert!(func.eval_precise(&one, &zero, &one, 15).is_err());
+    /// ```
+    pub fn eval_precise(
+        &self,
+        x: &DBig,
+        y: &DBig,
+        z: &DBig,
+        digits: usize,
+    ) -> Result<DBig, PrecisionError> {
+        eval_precise(&self.0, [x, y, z], digits)
+    }
+}
+
+#[test]
+fn test_precision() {
+    use std::str::FromStr;
+    let digits = 30;
+    let parse = |s: &str| DBig::from_str(s).unwrap();
+    let zero = parse("0");
+
+    assert_eq!(
+        F1D::from_str("pi").unwrap().eval_precise(&zero, digits),
+        Ok(parse("3.14159265358979323846264338328"))
+    );
+    assert_eq!(
+        F1D::from_str("e").unwrap().eval_precise(&zero, digits),
+        Ok(parse("2.71828182845904523536028747135"))
+    );
+
+    let x = parse("0.5");
+    let cases = [
+        ("sin(x)", "0.479425538604203000273287935216"),
+        ("cos(x)", "0.877582561890372716116281582604"),
+        ("tan(x)", "0.546302489843790513255179465780"),
+        ("asin(x)", "0.523598775598298873077107230547"),
+        ("acos(x)", "1.04719755119659774615421446109"),
+        ("atan(x)", "0.463647609000806116214256231461"),
+        ("sinh(x)", "0.521095305493747361622425626411"),
+        ("cosh(x)", "1.12762596520638078522622516140"),
+        ("tanh(x)", "0.462117157260009758502318483644"),
+        ("asinh(x)", "0.481211825059603447497758913424"),
+        ("atanh(x)", "0.549306144334054845697622618461"),
+        ("ln(x)", "-0.693147180559945309417232121458"),
+        ("x^(1/3)", "0.793700525984099737375852819636"),
+        ("x^x", "0.707106781186547524400844362105"),
+    ];
+    for (func, expected) in cases {
+        assert_eq!(
+            F1D::from_str(func).unwrap().eval_precise(&x, digits),
+            Ok(parse(expected)),
+            "{func}"
+        );
+    }
+
+    let func = F1D::from_str("sin(x)").unwrap();
+    assert_eq!(
+        func.eval_precise(&parse("1000"), digits),
+        Ok(parse("0.826879540532002560255887429109"))
+    );
+    assert_eq!(
+        F1D::from_str("acosh(x)").unwrap().eval_precise(&x, digits),
+        Err(PrecisionError::OutOfDomain("acosh(x)".to_string()))
+    );
+    assert_eq!(
+        F1D::from_str("x^(1/3)")
+            .unwrap()
+            .eval_precise(&parse("-8"), digits),
+        Ok(parse("-2"))
+    );
+
+    // No digits are lost to cancellation near 0
+    let x = parse("1e-30");
+    let cases = [
+        ("sinh(x)", "1.00000000000000000000000000000e-30"),
+        ("tanh(x)", "1.00000000000000000000000000000e-30"),
+        ("asinh(x)", "1.00000000000000000000000000000e-30"),
+        ("atanh(x)", "1.00000000000000000000000000000e-30"),
+        ("csch(x)", "1.00000000000000000000000000000e30"),
+        ("sinh(x)/x", "1"),
+    ];
+    for (func, expected) in cases {
+        assert_eq!(
+            F1D::from_str(func).unwrap().eval_precise(&x, digits),
+            Ok(parse(expected)),
+            "{func}"
+        );
+    }
+    let x = parse("-0.001");
+    let cases = [
+        ("sinh(x)", "-0.00100000016666667500000019841270"),
+        ("tanh(x)", "-0.000999999666666799999946031767901"),
+        ("asinh(x)", "-0.000999999833333408333288690506572"),
+        ("atanh(x)", "-0.00100000033333353333347619058730"),
+    ];
+    for (func, expected) in cases {
+        assert_eq!(
+            F1D::from_str(func).unwrap().eval_precise(&x, digits),
+            Ok(parse(expected)),
+            "{func}"
+        );
+    }
+}
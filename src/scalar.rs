@@ -0,0 +1,211 @@
+use crate::algebra::rational::Rational;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Numeric type functions can be evaluated on.
+///
+/// Only the functions without a default implementation are required, the reciprocal ones
+/// (cot, sec, csc...) are derived from them.
+/// ```
+/// use ruut_functions::{F1D, scalar::Scalar};
+/// use std::ops::{Add, Div, Mul, Neg, Sub};
+/// use std::str::FromStr;
+///
+/// // Counts how many operations are performed
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Counter(f64, u32);
+///
+/// macro_rules! impl_op {
+///     ($op:ident, $fn:ident) => {
+///         impl $op for Counter {
+///             type Output = Self;
+///             fn $fn(self, rhs: Self) -> Self {
+///                 Counter(self.0.$fn(rhs.0), self.1 + rhs.1 + 1)
+///             }
+///         }
+///     };
+/// }
+/// impl_op!(Add, add);
+/// impl_op!(Sub, sub);
+/// impl_op!(Mul, mul);
+/// impl_op!(Div, div);
+///
+/// impl Neg for Counter {
+///     type Output = Self;
+///     fn neg(self) -> Self {
+///         Counter(-self.0, self.1 + 1)
+///     }
+/// }
+///
+/// impl Scalar for Counter {
+///     fn from_f64(val: f64) -> Self { Counter(val, 0) }
+///     fn pow(self, exp: Self) -> Self { Counter(self.0.powf(exp.0), self.1 + exp.1 + 1) }
+///     fn ln(self) -> Self { Counter(self.0.ln(), self.1 + 1) }
+///     fn sin(self) -> Self { Counter(self.0.sin(), self.1 + 1) }
+///     fn cos(self) -> Self { Counter(self.0.cos(), self.1 + 1) }
+///     fn tan(self) -> Self { Counter(self.0.tan(), self.1 + 1) }
+///     fn asin(self) -> Self { Counter(self.0.asin(), self.1 + 1) }
+///     fn acos(self) -> Self { Counter(self.0.acos(), self.1 + 1) }
+///     fn atan(self) -> Self { Counter(self.0.atan(), self.1 + 1) }
+///     fn sinh(self) -> Self { Counter(self.0.sinh(), self.1 + 1) }
+///     fn cosh(self) -> Self { Counter(self.0.cosh(), self.1 + 1) }
+///     fn tanh(self) -> Self { Counter(self.0.tanh(), self.1 + 1) }
+///     fn asinh(self) -> Self { Counter(self.0.asinh(), self.1 + 1) }
+///     fn acosh(self) -> Self { Counter(self.0.acosh(), self.1 + 1) }
+///     fn atanh(self) -> Self { Counter(self.0.atanh(), self.1 + 1) }
+///     fn abs(self) -> Self { Counter(self.0.abs(), self.1 + 1) }
+/// }
+///
+/// let func = F1D::from_str("xsin(x)").unwrap();
+///
+/// assert_eq!(func.eval_scalar(Counter(2., 0)), Counter(2. * 2f64.sin(), 2));
+/// ```
+pub trait Scalar:
+    Clone
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Converts a f64 constant
+    fn from_f64(val: f64) -> Self;
+    /// Converts a rational constant
+    fn from_rational(val: &Rational) -> Self {
+        Self::from_f64(val.num() as f64) / Self::from_f64(val.den() as f64)
+    }
+    /// Euler's number
+    fn e() -> Self {
+        Self::from_f64(std::f64::consts::E)
+    }
+    /// Pi
+    fn pi() -> Self {
+        Self::from_f64(std::f64::consts::PI)
+    }
+
+    /// Raise to a power
+    fn pow(self, exp: Self) -> Self;
+    /// Natural logarithm
+    fn ln(self) -> Self;
+    /// Sine
+    fn sin(self) -> Self;
+    /// Cosine
+    fn cos(self) -> Self;
+    /// Tangent
+    fn tan(self) -> Self;
+    /// Cotangent
+    fn cot(self) -> Self {
+        Self::from_f64(1.) / self.tan()
+    }
+    /// Secant
+    fn sec(self) -> Self {
+        Self::from_f64(1.) / self.cos()
+    }
+    /// Cosecant
+    fn csc(self) -> Self {
+        Self::from_f64(1.) / self.sin()
+    }
+    /// Inverse of sine
+    fn asin(self) -> Self;
+    /// Inverse of cosine
+    fn acos(self) -> Self;
+    /// Inverse of tangent
+    fn atan(self) -> Self;
+    /// Hyperbolic sine
+    fn sinh(self) -> Self;
+    /// Hyperbolic cosine
+    fn cosh(self) -> Self;
+    /// Hyperbolic tangent
+    fn tanh(self) -> Self;
+    /// Hyperbolic cotangent
+    fn coth(self) -> Self {
+        Self::from_f64(1.) / self.tanh()
+    }
+    /// Hyperbolic secant
+    fn sech(self) -> Self {
+        Self::from_f64(1.) / self.cosh()
+    }
+    /// Hyperbolic cosecant
+    fn csch(self) -> Self {
+        Self::from_f64(1.) / self.sinh()
+    }
+    /// Inverse of hyperbolic sine
+    fn asinh(self) -> Self;
+    /// Inverse of hyperbolic cosine
+    fn acosh(self) -> Self;
+    /// Inverse of hyperbolic tangent
+    fn atanh(self) -> Self;
+    /// Absolute value
+    fn abs(self) -> Self;
+}
+
+macro_rules! impl_scalar{
+    (for $($t:ty),+) => {
+        $(impl Scalar for $t {
+            fn from_f64(val: f64) -> Self {
+                val as $t
+            }
+            fn pow(self, exp: Self) -> Self {
+                self.powf(exp)
+            }
+            fn ln(self) -> Self {
+                self.ln()
+            }
+            fn sin(self) -> Self {
+                self.sin()
+            }
+            fn cos(self) -> Self {
+                self.cos()
+            }
+            fn tan(self) -> Self {
+                self.tan()
+            }
+            fn asin(self) -> Self {
+                self.asin()
+            }
+            fn acos(self) -> Self {
+                self.acos()
+            }
+            fn atan(self) -> Self {
+                self.atan()
+            }
+            fn sinh(self) -> Self {
+                self.sinh()
+            }
+            fn cosh(self) -> Self {
+                self.cosh()
+            }
+            fn tanh(self) -> Self {
+                self.tanh()
+            }
+            fn asinh(self) -> Self {
+                self.asinh()
+            }
+            fn acosh(self) -> Self {
+                self.acosh()
+            }
+            fn atanh(self) -> Self {
+                self.atanh()
+            }
+            fn abs(self) -> Self {
+                self.abs()
+            }
+        })*
+    }
+}
+
+impl_scalar!(for f32, f64);
+
+#[test]
+fn test_scalar() {
+    use crate::{F1D, F2D, F3D};
+    use std::str::FromStr;
+
+    let func = F1D::from_str("sec(x)+csc(x)+cot(x)+atanh(x)").unwrap();
+    assert!((func.eval_scalar(0.5f32) - func.eval(0.5) as f32).abs() < 1e-5);
+
+    let func = F2D::from_str("sinh(x)cosh(y)/tanh(xy)+coth(x)sech(y)csch(x)").unwrap();
+    assert!((func.eval_scalar(0.5f32, 1.5f32) - func.eval(0.5, 1.5) as f32).abs() < 1e-5);
+
+    let func = F3D::from_str("(x+y)^z+pi*e+asinh(x)acosh(y)").unwrap();
+    assert!((func.eval_scalar(0.5f32, 1.5f32, 2f32) - func.eval(0.5, 1.5, 2.) as f32).abs() < 1e-5);
+}
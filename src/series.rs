@@ -0,0 +1,244 @@
+use crate::algebra::polynomials::Pol;
+use crate::algebra::rational::Rational;
+use crate::limit::{Direction, Limit, Point};
+use crate::simplify::binary;
+use crate::{Function, Operation, F1D, F2D, F3D};
+use std::error::Error;
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq)]
+/// Errors that can occur while expanding a function in series
+pub enum SeriesError {
+    /// A derivative of the function (the first one is the function itself) isn't finite at the
+    /// point
+    NotDifferentiable(String),
+    /// The factorial of the order doesn't fit in a rational, e.g. past 12 terms
+    Overflow,
+}
+
+impl Display for SeriesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotDifferentiable(func) => write!(f, "{func} isn't finite at the given point"),
+            Self::Overflow => write!(f, "The coefficients overflowed"),
+        }
+    }
+}
+impl Error for SeriesError {}
+
+/// Value of a function at a point, as an exact constant
+fn value_at(func: &Function, point: &[Rational]) -> Result<Function, SeriesError> {
+    let mut value = func.clone();
+    for (var, coord) in [Function::X, Function::Y, Function::Z].iter().zip(point) {
+        value = value.replace(var, &Function::Rational(coord.clone()));
+    }
+
+    // Checked before simplifying, which could drop a 0/0
+    if value.evaluate(&[0f64, 0., 0.]).is_finite() {
+        Ok(value.simplify())
+    } else {
+        Err(SeriesError::NotDifferentiable(func.to_string()))
+    }
+}
+
+/// (var - coord)
+fn delta(var: Function, coord: &Rational) -> Function {
+    match coord.num() {
+        0 => var,
+        _ => binary(Operation::Sub, var, Function::Rational(coord.clone())),
+    }
+}
+
+/// f(p) + ∇f(p)·d + d·H(p)d/2 with d = (vars - p)
+fn second_order(func: &Function, point: &[Rational]) -> Result<Function, SeriesError> {
+    let vars = [Function::X, Function::Y, Function::Z];
+    let deltas: Vec<_> = point
+        .iter()
+        .zip(vars)
+        .map(|(coord, var)| delta(var, coord))
+        .collect();
+
+    let mut result = value_at(func, point)?;
+    for i in 0..point.len() {
+        let gradient = func.derivative_by(i);
+        let term = binary(
+            Operation::Mul,
+            value_at(&gradient, point)?,
+            deltas[i].clone(),
+        );
+        result = binary(Operation::Add, result, term);
+
+        for j in i..point.len() {
+            let second = value_at(&gradient.derivative_by(j), point)?;
+            // Mixed terms appear twice in d·Hd
+            let coeff = Rational::new(if i == j { 1 } else { 2 }, 2);
+            let term = binary(
+                Operation::Mul,
+                binary(Operation::Mul, Function::Rational(coeff), second),
+                binary(Operation::Mul, deltas[i].clone(), deltas[j].clone()),
+            );
+            result = binary(Operation::Add, result, term);
+        }
+    }
+
+    Ok(result.simplify())
+}
+
+impl F1D {
+    /// Computes the Taylor polynomial of the given order around a, both as a function and as the
+    /// coefficients of the powers of (x - a). Derivatives with a removable singularity at a are
+    /// evaluated as limits
+    /// ```
+    /// use ruut_functions::{F1D, algebra::{polynomials::Pol, rational::Rational}};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("e^x").unwrap();
+    /// let (taylor, coeffs) = func.taylor(Rational::zero(), 3).unwrap();
+    ///
+    /// assert_eq!(taylor.to_string(), "x^3/6+x^2/2+x+1");
+    /// assert_eq!(coeffs, Pol::new(vec![1., 1., 0.5, 1. / 6.]));
+    /// ```
+    pub fn taylor(&self, a: Rational, order: u32) -> Result<(F1D, Pol), SeriesError> {
+        let mut result = Function::Rational(Rational::zero());
+        let mut coeffs = Vec::new();
+        let mut derivative = self.0.clone();
+        let mut factorial = Rational::new_from_int(1);
+
+        for k in 0..=order {
+            if k > 0 {
+                derivative = derivative.derivative(1, 0, 0).simplify();
+                factorial = factorial
+                    .checked_mul(Rational::new_from_int(k as i32))
+                    .ok_or(SeriesError::Overflow)?;
+            }
+            let value = match value_at(&derivative, std::slice::from_ref(&a)) {
+                Ok(value) => value,
+                Err(err) => {
+                    match F1D(derivative.clone()).limit(Point::Finite(a.clone()), Direction::Both) {
+                        Ok(Limit::Finite(F1D(value))) => value,
+                        _ => return Err(err),
+                    }
+                }
+            };
+
+            let coeff = binary(Operation::Div, value, Function::Rational(factorial.clone()));
+            coeffs.push(coeff.evaluate(&[0f64, 0., 0.]));
+            let power = binary(
+                Operation::Pow,
+                delta(Function::X, &a),
+                Function::Rational(Rational::new_from_int(k as i32)),
+            );
+            result = binary(Operation::Add, result, binary(Operation::Mul, coeff, power));
+        }
+
+        Ok((F1D(result.simplify()), Pol::new(coeffs)))
+    }
+}
+
+impl F2D {
+    /// Second order Taylor expansion around (x0, y0)
+    /// ```
+    /// use ruut_functions::{F2D, algebra::rational::Rational};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("e^xcos(y)").unwrap();
+    /// let expansion = func.second_order_taylor([Rational::zero(), Rational::zero()]).unwrap();
+    ///
+    /// assert_eq!(expansion, F2D::from_str("x^2/2-y^2/2+x+1").unwrap().simplify());
+    /// ```
+    pub fn second_order_taylor(&self, point: [Rational; 2]) -> Result<F2D, SeriesError> {
+        Ok(F2D(second_order(&self.0, &point)?))
+    }
+}
+
+impl F3D {
+    /// Second order Taylor expansion around (x0, y0, z0)
+    /// ```
+    /// use ruut_functions::{F3D, algebra::rational::Rational};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("xyz").unwrap();
+    /// let one = Rational::new_from_int(1);
+    /// let expansion = func.second_order_taylor([one.clone(), one.clone(), one]).unwrap();
+    ///
+    /// assert_eq!(expansion.eval(1., 1., 1.), 1.);
+    /// ```
+    pub fn second_order_taylor(&self, point: [Rational; 3]) -> Result<F3D, SeriesError> {
+        Ok(F3D(second_order(&self.0, &point)?))
+    }
+}
+
+#[test]
+fn test_series() {
+    use std::str::FromStr;
+
+    let cases = [
+        (
+            "sin(x)",
+            0,
+            7,
+            "-x^7/5040+x^5/120-x^3/6+x",
+            vec![0., 1., 0., -1. / 6., 0., 1. / 120., 0., -1. / 5040.],
+        ),
+        (
+            "ln(x)",
+            1,
+            3,
+            "(x-1)^3/3-(x-1)^2/2+x-1",
+            vec![0., 1., -0.5, 1. / 3.],
+        ),
+        (
+            "sin(x)/x",
+            0,
+            4,
+            "x^4/120-x^2/6+1",
+            vec![1., 0., -1. / 6., 0., 1. / 120.],
+        ),
+        (
+            "x^3+2x",
+            2,
+            4,
+            "(x-2)^3+6(x-2)^2+14(x-2)+12",
+            vec![12., 14., 6., 1., 0.],
+        ),
+    ];
+    for (func, a, order, expected, coeffs) in cases {
+        let func = F1D::from_str(func).unwrap();
+        let taylor = func.taylor(Rational::new_from_int(a), order).unwrap();
+        assert_eq!(
+            taylor,
+            (
+                F1D::from_str(expected).unwrap().simplify(),
+                Pol::new(coeffs)
+            ),
+            "{func}"
+        );
+    }
+
+    assert_eq!(
+        F1D::from_str("ln(x)").unwrap().taylor(Rational::zero(), 2),
+        Err(SeriesError::NotDifferentiable("ln(x)".to_string()))
+    );
+
+    // 12! is the largest factorial that fits
+    let func = F1D::from_str("sin(x)").unwrap();
+    let (taylor, _) = func.taylor(Rational::zero(), 12).unwrap();
+    assert!((taylor.eval(1.) - f64::sin(1.)).abs() < 1e-9);
+    assert_eq!(
+        func.taylor(Rational::zero(), 13),
+        Err(SeriesError::Overflow)
+    );
+
+    let func = F3D::from_str("e^(x+2y)z").unwrap();
+    let point = [
+        Rational::zero(),
+        Rational::zero(),
+        Rational::new_from_int(1),
+    ];
+    let expansion = func.second_order_taylor(point).unwrap();
+    // The error is third order in the distance from the point
+    for h in [1e-1, 1e-2] {
+        let (expected, value) = (func.eval(h, -h, 1. + h), expansion.eval(h, -h, 1. + h));
+        assert!((expected - value).abs() < 10. * h.powi(3));
+    }
+}
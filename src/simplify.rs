@@ -0,0 +1,600 @@
+use crate::algebra::rational::Rational;
+use crate::{Function, FunctionType, Operation, F1D, F2D, F3D};
+use std::cmp::Ordering;
+
+#[derive(Clone, Debug, PartialEq)]
+/// Product coeff * base1^exp1 * base2^exp2 ..., factors are sorted and have distinct bases
+pub(crate) struct Term {
+    pub(crate) coeff: Rational,
+    pub(crate) factors: Vec<(Function, Function)>,
+}
+
+/// Sorted sum of terms without like terms
+pub(crate) type Sum = Vec<Term>;
+
+fn rank(func: &Function) -> u8 {
+    match func {
+        Function::Rational(_) => 0,
+        Function::X => 1,
+        Function::Y => 2,
+        Function::Z => 3,
+        Function::E => 4,
+        Function::PI => 5,
+        Function::Special { .. } => 6,
+        Function::Binary { .. } => 7,
+    }
+}
+
+/// Total order between functions, used to sort commutative operands
+pub(crate) fn compare(first: &Function, second: &Function) -> Ordering {
+    match (first, second) {
+        (Function::Rational(a), Function::Rational(b)) => {
+            (a.num() as i64 * b.den() as i64).cmp(&(b.num() as i64 * a.den() as i64))
+        }
+        (
+            Function::Special { kind, argument },
+            Function::Special {
+                kind: other_kind,
+                argument: other_argument,
+            },
+        ) => (kind.clone() as u8)
+            .cmp(&(other_kind.clone() as u8))
+            .then_with(|| compare(argument, other_argument)),
+        (
+            Function::Binary { operation, terms },
+            Function::Binary {
+                operation: other_operation,
+                terms: other_terms,
+            },
+        ) => (operation.clone() as u8)
+            .cmp(&(other_operation.clone() as u8))
+            .then_with(|| compare(&terms.0, &other_terms.0))
+            .then_with(|| compare(&terms.1, &other_terms.1)),
+        _ => rank(first).cmp(&rank(second)),
+    }
+}
+
+pub(crate) fn contains_variable(func: &Function) -> bool {
+    match func {
+        Function::X | Function::Y | Function::Z => true,
+        Function::E | Function::PI | Function::Rational(_) => false,
+        Function::Binary { terms, .. } => {
+            contains_variable(&terms.0) || contains_variable(&terms.1)
+        }
+        Function::Special { argument, .. } => contains_variable(argument),
+    }
+}
+
+fn is_positive(func: &Function) -> bool {
+    match func {
+        Function::E | Function::PI => true,
+        Function::Rational(val) => val.num() > 0,
+        _ => false,
+    }
+}
+
+fn rational(func: &Function) -> Option<&Rational> {
+    match func {
+        Function::Rational(val) => Some(val),
+        _ => None,
+    }
+}
+
+pub(crate) fn binary(operation: Operation, left: Function, right: Function) -> Function {
+    Function::Binary {
+        operation,
+        terms: (Box::new(left), Box::new(right)),
+    }
+}
+
+impl Term {
+    pub(crate) fn constant(coeff: Rational) -> Self {
+        Term {
+            coeff,
+            factors: Vec::new(),
+        }
+    }
+
+    fn degree(&self) -> f64 {
+        self.factors
+            .iter()
+            .filter(|(base, _)| contains_variable(base))
+            .map(|(_, exp)| rational(exp).map_or(0., |exp| exp.eval()))
+            .sum()
+    }
+
+    /// Higher degrees first, then graded lexicographic order, constants last
+    fn order(&self, other: &Self) -> Ordering {
+        match (self.factors.is_empty(), other.factors.is_empty()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Greater,
+            (false, true) => return Ordering::Less,
+            _ => {}
+        }
+
+        other
+            .degree()
+            .partial_cmp(&self.degree())
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| {
+                for (first, second) in self.factors.iter().zip(&other.factors) {
+                    let ord =
+                        compare(&first.0, &second.0).then_with(|| compare(&second.1, &first.1));
+                    if ord != Ordering::Equal {
+                        return ord;
+                    }
+                }
+                self.factors.len().cmp(&other.factors.len())
+            })
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        let mut term = self.clone();
+        match term.coeff.clone().checked_mul(other.coeff.clone()) {
+            Some(coeff) => term.coeff = coeff,
+            None => term.push_factor(Function::Rational(other.coeff.clone()), one()),
+        }
+        for (base, exp) in &other.factors {
+            term.push_factor(base.clone(), exp.clone());
+        }
+        term
+    }
+
+    /// Multiplies by base^exp, combining powers of the same base
+    fn push_factor(&mut self, base: Function, exp: Function) {
+        match self.factors.iter().position(|(other, _)| *other == base) {
+            Some(idx) => {
+                let exp = add_exps(&self.factors[idx].1, &exp);
+                if rational(&exp).is_some_and(|exp| *exp == 0) {
+                    self.factors.remove(idx);
+                } else {
+                    self.factors[idx].1 = exp;
+                }
+            }
+            None => {
+                let idx = self
+                    .factors
+                    .partition_point(|(other, _)| compare(other, &base) == Ordering::Less);
+                self.factors.insert(idx, (base, exp));
+            }
+        }
+    }
+
+    pub(crate) fn to_function(&self) -> Function {
+        if self.factors.is_empty() {
+            return Function::Rational(self.coeff.clone());
+        }
+
+        let (mut num, mut den) = (Vec::new(), Vec::new());
+        for (base, exp) in &self.factors {
+            match rational(exp) {
+                Some(val) if val.num() < 0 => den.push(power(
+                    base.clone(),
+                    Function::Rational(Rational::new(-val.num(), val.den())),
+                )),
+                _ => num.push(power(base.clone(), exp.clone())),
+            }
+        }
+
+        let (coeff_num, coeff_den) = (self.coeff.num(), self.coeff.den());
+        if coeff_num != 1 || num.is_empty() {
+            num.insert(0, Function::Rational(Rational::new_from_int(coeff_num)));
+        }
+        if coeff_den != 1 {
+            den.insert(0, Function::Rational(Rational::new_from_int(coeff_den)));
+        }
+
+        let product = |factors: Vec<Function>| {
+            factors
+                .into_iter()
+                .reduce(|acc, factor| binary(Operation::Mul, acc, factor))
+        };
+        let num = product(num).unwrap_or_else(one);
+        match product(den) {
+            Some(den) => binary(Operation::Div, num, den),
+            None => num,
+        }
+    }
+}
+
+fn one() -> Function {
+    Function::Rational(Rational::new_from_int(1))
+}
+
+fn power(base: Function, exp: Function) -> Function {
+    if rational(&exp).is_some_and(|exp| *exp == 1) {
+        base
+    } else {
+        binary(Operation::Pow, base, exp)
+    }
+}
+
+fn add_exps(first: &Function, second: &Function) -> Function {
+    if let (Some(a), Some(b)) = (rational(first), rational(second)) {
+        if let Some(sum) = a.clone().checked_add(b.clone()) {
+            return Function::Rational(sum);
+        }
+    }
+    binary(Operation::Add, first.clone(), second.clone()).simplify()
+}
+
+fn mul_exps(first: &Function, second: &Function) -> Function {
+    if let (Some(a), Some(b)) = (rational(first), rational(second)) {
+        if let Some(prod) = a.clone().checked_mul(b.clone()) {
+            return Function::Rational(prod);
+        }
+    }
+    binary(Operation::Mul, first.clone(), second.clone()).simplify()
+}
+
+/// base^exp kept as a single factor
+fn atom(base: Function, exp: Function) -> Sum {
+    if let Some(val) = rational(&exp) {
+        if *val == 0 {
+            return vec![Term::constant(Rational::new_from_int(1))];
+        }
+        if let (true, Some(base)) = (*val == 1, rational(&base)) {
+            return constant(base.clone());
+        }
+    }
+
+    vec![Term {
+        coeff: Rational::new_from_int(1),
+        factors: vec![(base, exp)],
+    }]
+}
+
+fn constant(val: Rational) -> Sum {
+    if val == 0 {
+        Vec::new()
+    } else {
+        vec![Term::constant(val)]
+    }
+}
+
+/// Sorts the terms and combines like ones
+fn collect(mut terms: Vec<Term>) -> Sum {
+    terms.sort_by(|a, b| a.order(b));
+
+    let mut sum: Sum = Vec::with_capacity(terms.len());
+    for term in terms {
+        if let Some(last) = sum.last_mut() {
+            if last.factors == term.factors {
+                if let Some(coeff) = last.coeff.clone().checked_add(term.coeff.clone()) {
+                    last.coeff = coeff;
+                    if last.coeff == 0 {
+                        sum.pop();
+                    }
+                    continue;
+                }
+            }
+        }
+        sum.push(term);
+    }
+    sum
+}
+
+fn negate(sum: Sum) -> Sum {
+    sum.into_iter()
+        .map(|term| Term::constant(Rational::new_from_int(-1)).mul(&term))
+        .collect()
+}
+
+/// Products of sums are distributed only when expanding
+fn multiply(first: Sum, second: Sum, expand: bool) -> Sum {
+    if !expand && first.len() > 1 && second.len() > 1 {
+        let mut term = Term::constant(Rational::new_from_int(1));
+        term.push_factor(to_function(&first), one());
+        term.push_factor(to_function(&second), one());
+        return vec![term];
+    }
+
+    let mut terms = Vec::with_capacity(first.len() * second.len());
+    for a in &first {
+        for b in &second {
+            terms.push(a.mul(b));
+        }
+    }
+    collect(terms)
+}
+
+fn pow(base: Sum, exp: Function, expand: bool) -> Sum {
+    let val = match rational(&exp) {
+        Some(val) => val.clone(),
+        None => {
+            // e^(f)^g = e^(fg)
+            if let [Term { coeff, factors }] = base.as_slice() {
+                if let ([(inner, inner_exp)], true) = (factors.as_slice(), *coeff == 1) {
+                    if is_positive(inner) {
+                        return atom(inner.clone(), mul_exps(inner_exp, &exp));
+                    }
+                }
+            }
+            return atom(to_function(&base), exp);
+        }
+    };
+
+    if val == 0 {
+        return constant(Rational::new_from_int(1));
+    }
+    if val == 1 {
+        return base;
+    }
+
+    if let [term] = base.as_slice() {
+        if let Some(result) = pow_term(term, &val) {
+            return vec![result];
+        }
+    }
+    if base.is_empty() && val.num() > 0 {
+        return Vec::new();
+    }
+    if expand && val.is_integer() && val.num() > 1 {
+        let mut result = base.clone();
+        for _ in 1..val.num() {
+            result = multiply(result, base.clone(), true);
+        }
+        return result;
+    }
+
+    atom(to_function(&base), exp)
+}
+
+/// Distributes a rational power over a product, when it doesn't change the domain
+fn pow_term(term: &Term, exp: &Rational) -> Option<Term> {
+    let coeff = if exp.is_integer() {
+        term.coeff.clone().checked_pow(exp.num())?
+    } else {
+        if term.coeff.num() < 0 {
+            return None;
+        }
+        let root = term.coeff.clone().checked_root(exp.den() as u32)?;
+        root.checked_pow(exp.num())?
+    };
+
+    if !exp.is_integer() {
+        // (xy)^(1/2) isn't x^(1/2)y^(1/2) when both are negative, (x^2)^(1/2) isn't x
+        let unsafe_factors = term
+            .factors
+            .iter()
+            .filter(|(base, _)| !is_positive(base))
+            .collect::<Vec<_>>();
+        match unsafe_factors.as_slice() {
+            [] => {}
+            [(_, exp)] if rational(exp).is_some_and(|exp| *exp == 1) => {}
+            _ => return None,
+        }
+    }
+
+    let exp = Function::Rational(exp.clone());
+    let mut result = Term::constant(coeff);
+    for (base, inner_exp) in &term.factors {
+        result.push_factor(base.clone(), mul_exps(inner_exp, &exp));
+    }
+    Some(result)
+}
+
+fn special(kind: &FunctionType, argument: Function) -> Sum {
+    let zero = rational(&argument).is_some_and(|val| *val == 0);
+    match (kind, &argument) {
+        (FunctionType::Ln, Function::E) => return constant(Rational::new_from_int(1)),
+        (FunctionType::Ln, Function::Rational(val)) if *val == 1 => return Vec::new(),
+        (FunctionType::Abs, Function::Rational(val)) => {
+            return constant(Rational::new(val.num().abs(), val.den()))
+        }
+        (FunctionType::Cos | FunctionType::Cosh, _) if zero => {
+            return constant(Rational::new_from_int(1))
+        }
+        (
+            FunctionType::Sin
+            | FunctionType::Tan
+            | FunctionType::ASin
+            | FunctionType::ATan
+            | FunctionType::Sinh
+            | FunctionType::Tanh
+            | FunctionType::ASinh
+            | FunctionType::ATanh,
+            _,
+        ) if zero => return Vec::new(),
+        _ => {}
+    }
+
+    atom(
+        Function::Special {
+            kind: kind.clone(),
+            argument: Box::new(argument),
+        },
+        one(),
+    )
+}
+
+pub(crate) fn normalize(func: &Function, expand: bool) -> Sum {
+    match func {
+        Function::X | Function::Y | Function::Z | Function::E | Function::PI => {
+            atom(func.clone(), one())
+        }
+        Function::Rational(val) => constant(val.clone()),
+        Function::Binary { operation, terms } => {
+            let left = || normalize(&terms.0, expand);
+            let right = || normalize(&terms.1, expand);
+
+            match operation {
+                Operation::Add => collect([left(), right()].concat()),
+                Operation::Sub => collect([left(), negate(right())].concat()),
+                Operation::Mul => multiply(left(), right(), expand),
+                Operation::Div => multiply(
+                    left(),
+                    pow(
+                        right(),
+                        Function::Rational(Rational::new_from_int(-1)),
+                        expand,
+                    ),
+                    expand,
+                ),
+                Operation::Pow => pow(left(), terms.1.simplify(), expand),
+                Operation::Comp => atom(func.clone(), one()),
+            }
+        }
+        Function::Special { kind, argument } => {
+            let argument = if expand {
+                argument.expand()
+            } else {
+                argument.simplify()
+            };
+            special(kind, argument)
+        }
+    }
+}
+
+pub(crate) fn to_function(sum: &Sum) -> Function {
+    let mut result: Option<Function> = None;
+    for term in sum {
+        result = Some(match result {
+            None => term.to_function(),
+            Some(acc) if term.coeff.num() < 0 => {
+                let positive = Term::constant(Rational::new_from_int(-1)).mul(term);
+                binary(Operation::Sub, acc, positive.to_function())
+            }
+            Some(acc) => binary(Operation::Add, acc, term.to_function()),
+        });
+    }
+    result.unwrap_or(Function::Rational(Rational::zero()))
+}
+
+impl Function {
+    pub(crate) fn simplify(&self) -> Self {
+        to_function(&normalize(self, false))
+    }
+
+    pub(crate) fn expand(&self) -> Self {
+        to_function(&normalize(self, true))
+    }
+}
+
+impl F1D {
+    /// Rewrites the function in a canonical sum of products: like terms are combined, powers
+    /// of the same base merged, constants folded and operands sorted
+    /// ```
+    /// use ruut_functions::F1D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("x^2+3+x^2+2x+3x").unwrap();
+    ///
+    /// assert_eq!(func.simplify().to_string(), "2x^2+5x+3");
+    /// ```
+    pub fn simplify(&self) -> Self {
+        F1D(self.0.simplify())
+    }
+
+    /// Distributes products and integer powers
+    /// ```
+    /// use ruut_functions::F1D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("(x+2)(x-2)").unwrap();
+    ///
+    /// assert_eq!(func.expand().to_string(), "x^2-4");
+    /// ```
+    pub fn expand(&self) -> Self {
+        F1D(self.0.expand())
+    }
+}
+impl F2D {
+    /// Rewrites the function in a canonical sum of products: like terms are combined, powers
+    /// of the same base merged, constants folded and operands sorted
+    /// ```
+    /// use ruut_functions::F2D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("yx^2y/x-2xy^2").unwrap();
+    ///
+    /// assert_eq!(func.simplify().to_string(), "-xy^2");
+    /// ```
+    pub fn simplify(&self) -> Self {
+        F2D(self.0.simplify())
+    }
+
+    /// Distributes products and integer powers
+    /// ```
+    /// use ruut_functions::F2D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("(x+y)^3").unwrap();
+    ///
+    /// assert_eq!(func.expand().to_string(), "x^3+3x^2y+3xy^2+y^3");
+    /// ```
+    pub fn expand(&self) -> Self {
+        F2D(self.0.expand())
+    }
+}
+impl F3D {
+    /// Rewrites the function in a canonical sum of products: like terms are combined, powers
+    /// of the same base merged, constants folded and operands sorted
+    /// ```
+    /// use ruut_functions::F3D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("zyx+e^x*e^y-xyz").unwrap();
+    ///
+    /// assert_eq!(func.simplify().to_string(), "e^(x+y)");
+    /// ```
+    pub fn simplify(&self) -> Self {
+        F3D(self.0.simplify())
+    }
+
+    /// Distributes products and integer powers
+    /// ```
+    /// use ruut_functions::F3D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("x(y+z)^2").unwrap();
+    ///
+    /// assert_eq!(func.expand().to_string(), "xy^2+2xyz+xz^2");
+    /// ```
+    pub fn expand(&self) -> Self {
+        F3D(self.0.expand())
+    }
+}
+
+#[test]
+fn test_simplify() {
+    use std::str::FromStr;
+
+    let cases = [
+        ("x^2+3+x^2", "2x^2+3"),
+        ("2x+3x", "5x"),
+        ("x*x^2/x^4", "1/x"),
+        ("(x+1)(x+1)", "(x+1)^2"),
+        ("3(x+1)-x", "2x+3"),
+        ("sin(x)+sin(x)-2sin(x)", "0"),
+        ("(4x)^(1/2)", "2x^(1/2)"),
+        ("ln(e)+cos(0)+x/2", "x/2+2"),
+    ];
+    for (func, expected) in cases {
+        let simplified = F1D::from_str(func).unwrap().simplify();
+        assert_eq!(
+            simplified,
+            F1D::from_str(expected).unwrap().simplify(),
+            "{func}"
+        );
+        assert_eq!(simplified.to_string(), expected, "{func}");
+    }
+
+    // Domain preserving: (xy)^(1/2) isn't x^(1/2)y^(1/2)
+    let func = F2D::from_str("(xy)^(1/2)").unwrap();
+    assert_eq!(func.simplify().eval(-1., -4.), 2.);
+
+    // Commutative operands are sorted
+    assert_eq!(
+        F3D::from_str("zyx+yz").unwrap().simplify(),
+        F3D::from_str("yz+xyz").unwrap().simplify()
+    );
+
+    // Derivatives shrink and keep their value
+    let func = F2D::from_str("x^3y^2+sin(xy)x^2").unwrap();
+    let hessian_entry = func.derivative().x.derivative().y;
+    let simplified = hessian_entry.simplify();
+    assert!(simplified.to_string().len() < hessian_entry.to_string().len());
+    assert!((simplified.eval(0.7, 1.3) - hessian_entry.eval(0.7, 1.3)).abs() < 1e-9);
+    assert_eq!(simplified.simplify(), simplified);
+}
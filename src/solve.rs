@@ -0,0 +1,631 @@
+use crate::algebra::rational::Rational;
+use crate::simplify::{contains_variable, normalize, to_function, Term};
+use crate::{Function, FunctionType, Operation, Variable, F1D, F2D, F3D};
+use std::error::Error;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Condition on the other variables for a solution to exist
+pub enum Condition<F> {
+    /// The function isn't zero
+    NonZero(F),
+    /// The function is positive or zero
+    NonNegative(F),
+    /// The function is strictly positive
+    Positive(F),
+    /// The first function is between the other two, bounds included
+    Between(F, F, F),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Value of the variable solving an equation
+pub struct Solution<F> {
+    /// Value of the variable, depending on the other ones
+    pub value: F,
+    /// Conditions for the solution to exist, the ones that always hold are left out
+    pub conditions: Vec<Condition<F>>,
+    /// Any integer multiple of the period can be added to the value
+    pub period: Option<F>,
+}
+
+#[derive(Debug, PartialEq)]
+/// Errors that can occur while solving an equation
+pub enum SolveError {
+    /// The variable can't be isolated, or doesn't appear at all
+    NotSolvable(String),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotSolvable(func) => write!(f, "Can't solve {func} = 0 symbolically"),
+        }
+    }
+}
+impl Error for SolveError {}
+
+pub(crate) type Raw = Solution<Function>;
+
+fn rational(num: i32, den: i32) -> Function {
+    Function::Rational(Rational::new(num, den))
+}
+
+fn special(kind: FunctionType, argument: Function) -> Function {
+    Function::Special {
+        kind,
+        argument: Box::new(argument),
+    }
+}
+
+fn depends_on(func: &Function, var: &Function) -> bool {
+    match func {
+        Function::Binary { terms, .. } => depends_on(&terms.0, var) || depends_on(&terms.1, var),
+        Function::Special { argument, .. } => depends_on(argument, var),
+        _ => func == var,
+    }
+}
+
+pub(crate) fn occurrences(func: &Function, var: &Function) -> usize {
+    match func {
+        Function::Binary { terms, .. } => occurrences(&terms.0, var) + occurrences(&terms.1, var),
+        Function::Special { argument, .. } => occurrences(argument, var),
+        _ => usize::from(func == var),
+    }
+}
+
+/// Coefficients of a polynomial of degree at most 2 in var, from the constant term
+fn quadratic(func: &Function, var: &Function) -> Option<[Function; 3]> {
+    let mut coeffs: [Vec<Term>; 3] = Default::default();
+    for term in normalize(func, true) {
+        let mut degree = 0;
+        let mut rest = Term::constant(term.coeff);
+        for (base, exp) in term.factors {
+            match &exp {
+                Function::Rational(exp) if base == *var => match exp.num() {
+                    1 | 2 if exp.is_integer() => degree = exp.num() as usize,
+                    _ => return None,
+                },
+                _ if depends_on(&base, var) || depends_on(&exp, var) => return None,
+                _ => rest.factors.push((base, exp)),
+            }
+        }
+        coeffs[degree].push(rest);
+    }
+    Some(coeffs.map(|terms| to_function(&terms)))
+}
+
+fn solve_polynomial([c, b, a]: [Function; 3]) -> Vec<Raw> {
+    let zero = Function::Rational(Rational::zero());
+    if a == zero {
+        return vec![Raw {
+            value: -1 * c / b.clone(),
+            conditions: vec![Condition::NonZero(b)],
+            period: None,
+        }];
+    }
+
+    let discriminant = (b.clone() * b.clone() - 4 * a.clone() * c).simplify();
+    let conditions = vec![
+        Condition::NonZero(a.clone()),
+        Condition::NonNegative(discriminant.clone()),
+    ];
+    let root = |sign: i32| Raw {
+        value: (-1 * b.clone() + sign * discriminant.clone().powr(Rational::new(1, 2)))
+            / (2 * a.clone()),
+        conditions: conditions.clone(),
+        period: None,
+    };
+    if discriminant == zero {
+        vec![root(0)]
+    } else {
+        vec![root(-1), root(1)]
+    }
+}
+
+/// rhs^exp inverting an odd integer power, so exp is 1/n with n odd, real also for negative
+/// rhs. Fractional powers of negative numbers aren't evaluated, so roots are taken of the
+/// positive and negative parts of rhs, (abs(rhs)+rhs)/2 and (abs(rhs)-rhs)/2
+fn odd_power(rhs: Function, exp: Rational) -> Function {
+    if exp.is_integer() {
+        return rhs.powr(exp);
+    }
+    let abs = special(FunctionType::Abs, rhs.clone());
+    let positive = (abs.clone() + rhs.clone()) / rational(2, 1);
+    let negative = (abs - rhs) / rational(2, 1);
+    let magnitude = Rational::new(exp.num().abs(), exp.den());
+    let root = positive.powr(magnitude.clone()) - negative.powr(magnitude);
+    match exp.num() > 0 {
+        true => root,
+        false => rational(1, 1) / root,
+    }
+}
+
+/// Peels the functions around the only occurrence of var from lhs = rhs. None if some step
+/// can't be inverted, or would need to invert a non linear function of a periodic solution
+pub(crate) fn isolate(
+    lhs: &Function,
+    rhs: Function,
+    var: &Function,
+    mut conditions: Vec<Condition<Function>>,
+    period: Option<Function>,
+) -> Option<Vec<Raw>> {
+    if lhs == var {
+        return Some(vec![Raw {
+            value: rhs,
+            conditions,
+            period,
+        }]);
+    }
+    // Linear steps move the period along, the others would need every shifted solution
+    let linear = period.is_none();
+    let mut branches = Vec::new();
+
+    match lhs {
+        Function::Binary { operation, terms } => {
+            let (a, b) = (&*terms.0, &*terms.1);
+            let first = depends_on(a, var);
+            match operation {
+                Operation::Add if first => {
+                    return isolate(a, rhs - b.clone(), var, conditions, period)
+                }
+                Operation::Add => return isolate(b, rhs - a.clone(), var, conditions, period),
+                Operation::Sub if first => {
+                    return isolate(a, rhs + b.clone(), var, conditions, period)
+                }
+                Operation::Sub => return isolate(b, a.clone() - rhs, var, conditions, period),
+                Operation::Mul => {
+                    let (u, c) = if first { (a, b) } else { (b, a) };
+                    conditions.push(Condition::NonZero(c.clone()));
+                    let period = period.map(|period| period / c.clone());
+                    return isolate(u, rhs / c.clone(), var, conditions, period);
+                }
+                Operation::Div if first => {
+                    conditions.push(Condition::NonZero(b.clone()));
+                    let period = period.map(|period| period * b.clone());
+                    return isolate(a, rhs * b.clone(), var, conditions, period);
+                }
+                Operation::Div if linear => {
+                    conditions.push(Condition::NonZero(rhs.clone()));
+                    return isolate(b, a.clone() / rhs, var, conditions, None);
+                }
+                Operation::Pow if !linear => return None,
+                Operation::Pow if first => match b {
+                    Function::Rational(exp) => {
+                        let p = exp.num();
+                        let inverse = Rational::new(exp.den(), p);
+                        let sign = if p > 0 {
+                            Condition::NonNegative(rhs.clone())
+                        } else {
+                            Condition::Positive(rhs.clone())
+                        };
+                        let root = rhs.clone().powr(inverse.clone());
+                        if !exp.is_integer() {
+                            // Fractional powers are only evaluated for non negative bases
+                            branches.push((a, root, vec![sign]));
+                        } else if p % 2 == 0 {
+                            branches.push((a, root.clone(), vec![sign.clone()]));
+                            branches.push((a, -1 * root, vec![sign]));
+                        } else {
+                            let nonzero = match p > 0 {
+                                true => vec![],
+                                false => vec![Condition::NonZero(rhs.clone())],
+                            };
+                            branches.push((a, odd_power(rhs, inverse), nonzero));
+                        }
+                    }
+                    _ => branches.push((
+                        a,
+                        rhs.clone().pow(rational(1, 1) / b.clone()),
+                        vec![Condition::Positive(rhs)],
+                    )),
+                },
+                Operation::Pow => {
+                    let log = special(FunctionType::Ln, rhs.clone());
+                    let mut extra = vec![Condition::Positive(rhs)];
+                    let value = match a {
+                        Function::E => log,
+                        _ => {
+                            let base_log = special(FunctionType::Ln, a.clone());
+                            // Dropped by finalize when the base is a constant
+                            extra.push(Condition::Positive(a.clone()));
+                            extra.push(Condition::NonZero(base_log.clone()));
+                            log / base_log
+                        }
+                    };
+                    branches.push((b, value, extra));
+                }
+                _ => return None,
+            }
+        }
+        Function::Special { .. } if !linear => return None,
+        Function::Special { kind, argument } => {
+            let u = &**argument;
+            let pi = || Function::PI;
+            let unit =
+                |rhs: &Function| Condition::Between(rhs.clone(), rational(-1, 1), rational(1, 1));
+            let reciprocal = |kind: FunctionType, rhs: Function, mut conditions: Vec<_>| {
+                conditions.push(Condition::NonZero(rhs.clone()));
+                isolate(
+                    &special(kind, u.clone()),
+                    rational(1, 1) / rhs,
+                    var,
+                    conditions,
+                    None,
+                )
+            };
+            let between = |rhs: &Function, lo: Function, hi: Function| {
+                Condition::Between(rhs.clone(), lo, hi)
+            };
+
+            match kind {
+                FunctionType::Sec => return reciprocal(FunctionType::Cos, rhs, conditions),
+                FunctionType::Csc => return reciprocal(FunctionType::Sin, rhs, conditions),
+                FunctionType::Coth => return reciprocal(FunctionType::Tanh, rhs, conditions),
+                FunctionType::Sech => return reciprocal(FunctionType::Cosh, rhs, conditions),
+                FunctionType::Csch => return reciprocal(FunctionType::Sinh, rhs, conditions),
+                FunctionType::Cot => {
+                    // cot(pi/2 - t) = tan(t), also covering cot(u) = 0
+                    let value = pi() / rational(2, 1) - special(FunctionType::ATan, rhs);
+                    return isolate(u, value, var, conditions, Some(pi()));
+                }
+                FunctionType::Sin => {
+                    conditions.push(unit(&rhs));
+                    let asin = special(FunctionType::ASin, rhs);
+                    let period = || Some(2 * pi());
+                    let mut solutions =
+                        isolate(u, asin.clone(), var, conditions.clone(), period())?;
+                    solutions.extend(isolate(u, pi() - asin, var, conditions, period())?);
+                    return Some(solutions);
+                }
+                FunctionType::Cos => {
+                    conditions.push(unit(&rhs));
+                    let acos = special(FunctionType::ACos, rhs);
+                    let period = || Some(2 * pi());
+                    let mut solutions =
+                        isolate(u, acos.clone(), var, conditions.clone(), period())?;
+                    solutions.extend(isolate(u, -1 * acos, var, conditions, period())?);
+                    return Some(solutions);
+                }
+                FunctionType::Tan => {
+                    let value = special(FunctionType::ATan, rhs);
+                    return isolate(u, value, var, conditions, Some(pi()));
+                }
+                FunctionType::Ln => branches.push((u, Function::E.pow(rhs), vec![])),
+                FunctionType::ASin => branches.push((
+                    u,
+                    special(FunctionType::Sin, rhs.clone()),
+                    vec![between(
+                        &rhs,
+                        -1 * pi() / rational(2, 1),
+                        pi() / rational(2, 1),
+                    )],
+                )),
+                FunctionType::ACos => branches.push((
+                    u,
+                    special(FunctionType::Cos, rhs.clone()),
+                    vec![between(&rhs, rational(0, 1), pi())],
+                )),
+                FunctionType::ATan => branches.push((
+                    u,
+                    special(FunctionType::Tan, rhs.clone()),
+                    vec![between(
+                        &rhs,
+                        -1 * pi() / rational(2, 1),
+                        pi() / rational(2, 1),
+                    )],
+                )),
+                FunctionType::Sinh => branches.push((u, special(FunctionType::ASinh, rhs), vec![])),
+                FunctionType::Cosh => {
+                    let acosh = special(FunctionType::ACosh, rhs.clone());
+                    let condition = Condition::NonNegative(rhs - rational(1, 1));
+                    branches.push((u, acosh.clone(), vec![condition.clone()]));
+                    branches.push((u, -1 * acosh, vec![condition]));
+                }
+                FunctionType::Tanh => branches.push((
+                    u,
+                    special(FunctionType::ATanh, rhs.clone()),
+                    vec![unit(&rhs)],
+                )),
+                FunctionType::ASinh => branches.push((u, special(FunctionType::Sinh, rhs), vec![])),
+                FunctionType::ACosh => branches.push((
+                    u,
+                    special(FunctionType::Cosh, rhs.clone()),
+                    vec![Condition::NonNegative(rhs)],
+                )),
+                FunctionType::ATanh => branches.push((u, special(FunctionType::Tanh, rhs), vec![])),
+                FunctionType::Abs => {
+                    let condition = Condition::NonNegative(rhs.clone());
+                    branches.push((u, rhs.clone(), vec![condition.clone()]));
+                    branches.push((u, -1 * rhs, vec![condition]));
+                }
+            }
+        }
+        _ => return None,
+    }
+
+    let mut solutions = Vec::new();
+    for (lhs, rhs, extra) in branches {
+        let mut conditions = conditions.clone();
+        conditions.extend(extra);
+        solutions.extend(isolate(lhs, rhs, var, conditions, None)?);
+    }
+    Some(solutions)
+}
+
+/// Value of a function without variables
+fn constant(func: &Function) -> Option<f64> {
+    (!contains_variable(func)).then(|| func.evaluate(&[0f64, 0., 0.]))
+}
+
+/// Some(true) if the condition always holds, Some(false) if it never does
+fn check(condition: &Condition<Function>) -> Option<bool> {
+    const TOLERANCE: f64 = 1e-12;
+    Some(match condition {
+        Condition::NonZero(func) => constant(func)?.abs() > TOLERANCE,
+        Condition::NonNegative(func) => constant(func)? >= -TOLERANCE,
+        Condition::Positive(func) => constant(func)? > TOLERANCE,
+        Condition::Between(func, lo, hi) => {
+            let value = constant(func)?;
+            value >= constant(lo)? - TOLERANCE && value <= constant(hi)? + TOLERANCE
+        }
+    })
+}
+
+/// Simplifies the solution and drops the conditions that always hold, None if one never does
+pub(crate) fn finalize(solution: Raw) -> Option<Raw> {
+    let simplify = |condition: Condition<Function>| match condition {
+        Condition::NonZero(func) => Condition::NonZero(func.simplify()),
+        Condition::NonNegative(func) => Condition::NonNegative(func.simplify()),
+        Condition::Positive(func) => Condition::Positive(func.simplify()),
+        Condition::Between(func, lo, hi) => {
+            Condition::Between(func.simplify(), lo.simplify(), hi.simplify())
+        }
+    };
+
+    let mut conditions: Vec<Condition<Function>> = Vec::new();
+    for condition in solution.conditions.into_iter().map(simplify) {
+        match check(&condition) {
+            Some(true) => {}
+            Some(false) => return None,
+            None if !conditions.contains(&condition) => conditions.push(condition),
+            None => {}
+        }
+    }
+
+    let value = solution.value.simplify();
+    if constant(&value).is_some_and(|value| !value.is_finite()) {
+        return None;
+    }
+    Some(Raw {
+        value,
+        conditions,
+        period: solution.period.map(|period| period.simplify()),
+    })
+}
+
+fn solve(func: &Function, var: Variable) -> Result<Vec<Raw>, SolveError> {
+    let var = match var {
+        Variable::X => Function::X,
+        Variable::Y => Function::Y,
+        Variable::Z => Function::Z,
+    };
+    let zero = || Function::Rational(Rational::zero());
+    let not_solvable = || SolveError::NotSolvable(func.to_string());
+    if !depends_on(func, &var) {
+        return Err(not_solvable());
+    }
+
+    let solutions = if let Some(coeffs) = quadratic(func, &var) {
+        solve_polynomial(coeffs)
+    } else {
+        // Simplifying could merge repeated occurrences
+        [func.clone(), func.simplify()]
+            .iter()
+            .filter(|func| occurrences(func, &var) == 1)
+            .find_map(|func| isolate(func, zero(), &var, Vec::new(), None))
+            .ok_or_else(not_solvable)?
+    };
+
+    let mut result: Vec<Raw> = Vec::new();
+    for solution in solutions.into_iter().filter_map(finalize) {
+        let duplicate = result.iter().any(|other| {
+            other.conditions == solution.conditions
+                && (other.value == solution.value
+                    || matches!(
+                        (constant(&other.value), constant(&solution.value)),
+                        (Some(a), Some(b)) if (a - b).abs() < 1e-12
+                    ))
+        });
+        if !duplicate {
+            result.push(solution);
+        }
+    }
+    Ok(result)
+}
+
+pub(crate) fn wrap<F>(solution: Raw, wrap: fn(Function) -> F) -> Solution<F> {
+    let conditions = solution
+        .conditions
+        .into_iter()
+        .map(|condition| match condition {
+            Condition::NonZero(func) => Condition::NonZero(wrap(func)),
+            Condition::NonNegative(func) => Condition::NonNegative(wrap(func)),
+            Condition::Positive(func) => Condition::Positive(wrap(func)),
+            Condition::Between(func, lo, hi) => Condition::Between(wrap(func), wrap(lo), wrap(hi)),
+        })
+        .collect();
+    Solution {
+        value: wrap(solution.value),
+        conditions,
+        period: solution.period.map(wrap),
+    }
+}
+
+impl F1D {
+    /// Solves self = 0 for the variable: polynomials up to degree 2 and equations where it
+    /// appears once, inverting the functions around it. An empty list means there are no
+    /// solutions
+    /// ```
+    /// use ruut_functions::{F1D, Variable};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("x^2-5x+6").unwrap();
+    /// let solutions = func.solve_for(Variable::X).unwrap();
+    ///
+    /// assert_eq!(solutions[0].value.to_string(), "2");
+    /// assert_eq!(solutions[1].value.to_string(), "3");
+    ///
+    /// let func = F1D::from_str("2sin(x)-1").unwrap();
+    /// let solutions = func.solve_for(Variable::X).unwrap();
+    /// assert_eq!(solutions[0].value.to_string(), "asin(1/2)");
+    /// assert_eq!(solutions[0].period, Some(F1D::from_str("2pi").unwrap().simplify()));
+    /// ```
+    pub fn solve_for(&self, var: Variable) -> Result<Vec<Solution<F1D>>, SolveError> {
+        Ok(solve(&self.0, var)?
+            .into_iter()
+            .map(|solution| wrap(solution, F1D))
+            .collect())
+    }
+}
+
+impl F2D {
+    /// Solves self = 0 for the variable: polynomials up to degree 2 and equations where it
+    /// appears once, inverting the functions around it. The solutions depend on the other
+    /// variable, and come with the conditions for them to exist
+    /// ```
+    /// use ruut_functions::{F2D, Variable, solve::Condition};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("ln(y)-x").unwrap();
+    /// let solutions = func.solve_for(Variable::Y).unwrap();
+    /// assert_eq!(solutions[0].value.to_string(), "e^x");
+    ///
+    /// let func = F2D::from_str("y^2-x").unwrap();
+    /// let solutions = func.solve_for(Variable::Y).unwrap();
+    /// assert_eq!(solutions.len(), 2);
+    /// assert_eq!(
+    ///     solutions[0].conditions,
+    ///     vec![Condition::NonNegative(F2D::from_str("4x").unwrap().simplify())]
+    /// );
+    /// ```
+    pub fn solve_for(&self, var: Variable) -> Result<Vec<Solution<F2D>>, SolveError> {
+        Ok(solve(&self.0, var)?
+            .into_iter()
+            .map(|solution| wrap(solution, F2D))
+            .collect())
+    }
+}
+
+impl F3D {
+    /// Solves self = 0 for the variable: polynomials up to degree 2 and equations where it
+    /// appears once, inverting the functions around it. The solutions depend on the other
+    /// variables, and come with the conditions for them to exist
+    /// ```
+    /// use ruut_functions::{F3D, Variable, solve::SolveError};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("xz+y").unwrap();
+    /// let solutions = func.solve_for(Variable::Z).unwrap();
+    /// assert_eq!(solutions[0].value, F3D::from_str("-y/x").unwrap().simplify());
+    ///
+    /// let func = F3D::from_str("z+sin(z)-x").unwrap();
+    /// assert!(matches!(func.solve_for(Variable::Z), Err(SolveError::NotSolvable(_))));
+    /// ```
+    pub fn solve_for(&self, var: Variable) -> Result<Vec<Solution<F3D>>, SolveError> {
+        Ok(solve(&self.0, var)?
+            .into_iter()
+            .map(|solution| wrap(solution, F3D))
+            .collect())
+    }
+}
+
+#[test]
+fn test_solve() {
+    use std::str::FromStr;
+
+    // Every solution is a zero of the function
+    let cases = [
+        ("3x-7", 1),
+        ("x^2-2", 2),
+        ("x^2-2x+1", 1),
+        ("x^2+1", 0),
+        ("2x^2+3x-2", 2),
+        ("x^3+8", 1),
+        ("x^(1/2)-3", 1),
+        ("x^(-2)-4", 2),
+        ("e^(2x)-5", 1),
+        ("2^x-8", 1),
+        ("ln(x+1)-2", 1),
+        ("cos(x)-1/2", 2),
+        ("tan(3x)-1", 1),
+        ("sec(x)-2", 2),
+        ("abs(x-1)-3", 2),
+        ("1/(x+2)-4", 1),
+        ("cosh(x)-2", 2),
+        ("atan(x)-1", 1),
+        ("sin(x)-2", 0),
+        ("atan(x)-2", 0),
+        ("cot(x)", 1),
+        ("cot(2x)+1", 1),
+        ("x^(3/5)+1", 0),
+        ("x^(3/5)-8", 1),
+        ("x^(2/3)-4", 1),
+    ];
+    for (func, count) in cases {
+        let func = F1D::from_str(func).unwrap();
+        let solutions = func.solve_for(Variable::X).unwrap();
+        assert_eq!(solutions.len(), count, "{func}");
+        for solution in solutions {
+            let x = solution.value.eval(0.);
+            assert!(func.eval(x).abs() < 1e-9, "{func} {}", solution.value);
+            if let Some(period) = solution.period {
+                assert!(func.eval(x + period.eval(0.)).abs() < 1e-9, "{func}");
+            }
+        }
+    }
+
+    assert_eq!(
+        F1D::from_str("x^3+x+1").unwrap().solve_for(Variable::X),
+        Err(SolveError::NotSolvable("x^3+x+1".to_string()))
+    );
+    assert!(F1D::from_str("sin(x^2)")
+        .unwrap()
+        .solve_for(Variable::X)
+        .is_err());
+    assert!(F1D::from_str("x").unwrap().solve_for(Variable::Y).is_err());
+
+    // Solutions depending on the other variables hold where their conditions do
+    let func = F3D::from_str("zx^2+y-1").unwrap();
+    let solutions = func.solve_for(Variable::X).unwrap();
+    assert_eq!(solutions.len(), 2);
+    for (y, z) in [(0.5, 2.), (-3., 0.5)] {
+        for solution in &solutions {
+            let x = solution.value.eval(0., y, z);
+            assert!(func.eval(x, y, z).abs() < 1e-9);
+        }
+    }
+
+    let func = F2D::from_str("asin(xy)-1/2").unwrap();
+    let solutions = func.solve_for(Variable::Y).unwrap();
+    assert_eq!(solutions.len(), 1);
+    assert_eq!(
+        solutions[0].conditions,
+        vec![Condition::NonZero(F2D::from_str("x").unwrap())]
+    );
+    let y = solutions[0].value.eval(2., 0.);
+    assert!(func.eval(2., y).abs() < 1e-12);
+
+    // A variable base has to be positive and different from 1
+    let func = F2D::from_str("y^x-2").unwrap();
+    let solutions = func.solve_for(Variable::X).unwrap();
+    assert_eq!(solutions.len(), 1);
+    assert_eq!(
+        solutions[0].conditions,
+        vec![
+            Condition::Positive(F2D::from_str("y").unwrap()),
+            Condition::NonZero(F2D::from_str("ln(y)").unwrap()),
+        ]
+    );
+    let x = solutions[0].value.eval(0., 3.);
+    assert!(func.eval(x, 3.).abs() < 1e-12);
+}
@@ -0,0 +1,528 @@
+use crate::algebra::rational::Rational;
+use crate::factor::rational_gcd;
+use crate::simplify::{binary, normalize, to_function, Term};
+use crate::{Function, FunctionType, Operation, F1D, F2D, F3D};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Form `simplify_trig` rewrites to
+pub enum TrigForm {
+    /// Only sin, cos, sinh and cosh of the smallest angles, with sin and sinh to at most the
+    /// first power: the same for equal polynomials in them
+    SinCos,
+    /// Shortest printed form among the ones tried, reciprocal functions included
+    Shortest,
+}
+
+/// Highest multiple of an angle that gets expanded
+const MAX_MULTIPLE: i32 = 8;
+
+fn rational(num: i32, den: i32) -> Function {
+    Function::Rational(Rational::new(num, den))
+}
+
+fn special(kind: FunctionType, argument: Function) -> Function {
+    Function::Special {
+        kind,
+        argument: Box::new(argument),
+    }
+}
+
+/// kind(argument), cancelling it against an inverse function
+fn apply(kind: FunctionType, argument: Function) -> Function {
+    let inner = match &argument {
+        Function::Special { kind, argument } => Some((kind, &**argument)),
+        _ => None,
+    };
+    let square = |u: &Function| binary(Operation::Pow, u.clone(), rational(2, 1));
+    let root =
+        |func: Function, exp: Rational| binary(Operation::Pow, func, Function::Rational(exp));
+
+    match (&kind, inner) {
+        (FunctionType::Sin, Some((FunctionType::ASin, u)))
+        | (FunctionType::Cos, Some((FunctionType::ACos, u)))
+        | (FunctionType::Tan, Some((FunctionType::ATan, u)))
+        | (FunctionType::Sinh, Some((FunctionType::ASinh, u)))
+        | (FunctionType::Cosh, Some((FunctionType::ACosh, u)))
+        | (FunctionType::Tanh, Some((FunctionType::ATanh, u)))
+        | (FunctionType::ASinh, Some((FunctionType::Sinh, u)))
+        | (FunctionType::ATanh, Some((FunctionType::Tanh, u))) => u.clone(),
+        (FunctionType::Cos, Some((FunctionType::ASin, u)))
+        | (FunctionType::Sin, Some((FunctionType::ACos, u))) => root(
+            binary(Operation::Sub, rational(1, 1), square(u)),
+            Rational::new(1, 2),
+        ),
+        (FunctionType::Cos, Some((FunctionType::ATan, u))) => root(
+            binary(Operation::Add, rational(1, 1), square(u)),
+            Rational::new(-1, 2),
+        ),
+        (FunctionType::Sin, Some((FunctionType::ATan, u))) => binary(
+            Operation::Mul,
+            u.clone(),
+            root(
+                binary(Operation::Add, rational(1, 1), square(u)),
+                Rational::new(-1, 2),
+            ),
+        ),
+        (FunctionType::Cosh, Some((FunctionType::ASinh, u))) => root(
+            binary(Operation::Add, square(u), rational(1, 1)),
+            Rational::new(1, 2),
+        ),
+        (FunctionType::Sinh, Some((FunctionType::ACosh, u))) => root(
+            binary(Operation::Sub, square(u), rational(1, 1)),
+            Rational::new(1, 2),
+        ),
+        _ => special(kind, argument),
+    }
+}
+
+/// Removes the reciprocal functions and the inverse/forward pairs
+fn to_sin_cos(func: &Function) -> Function {
+    match func {
+        Function::Binary { operation, terms } => binary(
+            operation.clone(),
+            to_sin_cos(&terms.0),
+            to_sin_cos(&terms.1),
+        ),
+        Function::Special { kind, argument } => {
+            let argument = to_sin_cos(argument).simplify();
+            let ratio = |num: FunctionType, den: FunctionType| {
+                binary(
+                    Operation::Div,
+                    apply(num, argument.clone()),
+                    apply(den, argument.clone()),
+                )
+            };
+            let reciprocal = |den: FunctionType| {
+                binary(Operation::Div, rational(1, 1), apply(den, argument.clone()))
+            };
+
+            match kind {
+                FunctionType::Tan => ratio(FunctionType::Sin, FunctionType::Cos),
+                FunctionType::Cot => ratio(FunctionType::Cos, FunctionType::Sin),
+                FunctionType::Sec => reciprocal(FunctionType::Cos),
+                FunctionType::Csc => reciprocal(FunctionType::Sin),
+                FunctionType::Tanh => ratio(FunctionType::Sinh, FunctionType::Cosh),
+                FunctionType::Coth => ratio(FunctionType::Cosh, FunctionType::Sinh),
+                FunctionType::Sech => reciprocal(FunctionType::Cosh),
+                FunctionType::Csch => reciprocal(FunctionType::Sinh),
+                _ => apply(kind.clone(), argument),
+            }
+        }
+        _ => func.clone(),
+    }
+}
+
+fn is_angle_function(kind: &FunctionType) -> bool {
+    matches!(
+        kind,
+        FunctionType::Sin | FunctionType::Cos | FunctionType::Sinh | FunctionType::Cosh
+    )
+}
+
+/// Splits an angle into coefficient and rest: 3x/2 is (3/2, x)
+fn split_angle(argument: &Function) -> (Rational, Function) {
+    match normalize(argument, false).as_slice() {
+        [term] if !term.factors.is_empty() => {
+            let rest = Term {
+                coeff: Rational::new_from_int(1),
+                factors: term.factors.clone(),
+            };
+            (term.coeff.clone(), rest.to_function())
+        }
+        _ => (Rational::new_from_int(1), argument.clone()),
+    }
+}
+
+/// Smallest angle of which every angle with the same rest is a multiple
+fn collect_angles(func: &Function, angles: &mut Vec<(Function, Rational)>) {
+    match func {
+        Function::Binary { terms, .. } => {
+            collect_angles(&terms.0, angles);
+            collect_angles(&terms.1, angles);
+        }
+        Function::Special { kind, argument } => {
+            collect_angles(argument, angles);
+            if is_angle_function(kind) {
+                let (coeff, rest) = split_angle(argument);
+                match angles.iter_mut().find(|(other, _)| *other == rest) {
+                    Some((_, gcd)) => {
+                        if let Some(new) = rational_gcd(gcd, &coeff) {
+                            *gcd = new;
+                        }
+                    }
+                    None => angles.push((rest, Rational::new(coeff.num().abs(), coeff.den()))),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// sin(nb), cos(nb) (or sinh and cosh) through the angle addition formulas
+fn multiple_angle(n: i32, base: &Function, hyperbolic: bool) -> (Function, Function) {
+    let (sin, cos) = match hyperbolic {
+        false => (FunctionType::Sin, FunctionType::Cos),
+        true => (FunctionType::Sinh, FunctionType::Cosh),
+    };
+    let (s, c) = (special(sin, base.clone()), special(cos, base.clone()));
+
+    let (mut sin_n, mut cos_n) = (s.clone(), c.clone());
+    for _ in 1..n.abs() {
+        let mul = |a: &Function, b: &Function| binary(Operation::Mul, a.clone(), b.clone());
+        let next_sin = binary(Operation::Add, mul(&sin_n, &c), mul(&cos_n, &s));
+        let next_cos = match hyperbolic {
+            false => binary(Operation::Sub, mul(&cos_n, &c), mul(&sin_n, &s)),
+            true => binary(Operation::Add, mul(&cos_n, &c), mul(&sin_n, &s)),
+        };
+        (sin_n, cos_n) = (next_sin, next_cos);
+    }
+    // Sine is odd, cosine is even
+    if n < 0 {
+        sin_n = binary(Operation::Mul, rational(-1, 1), sin_n);
+    }
+    (sin_n, cos_n)
+}
+
+/// Writes every angle as a multiple of the smallest one
+fn expand_angles(func: &Function, angles: &[(Function, Rational)]) -> Function {
+    match func {
+        Function::Binary { operation, terms } => binary(
+            operation.clone(),
+            expand_angles(&terms.0, angles),
+            expand_angles(&terms.1, angles),
+        ),
+        Function::Special { kind, argument } => {
+            let argument = expand_angles(argument, angles);
+            if !is_angle_function(kind) {
+                return special(kind.clone(), argument);
+            }
+
+            let (coeff, rest) = split_angle(&argument);
+            let multiple = angles
+                .iter()
+                .find(|(other, _)| *other == rest)
+                .and_then(|(_, gcd)| coeff.checked_div(gcd.clone()));
+            match multiple {
+                Some(n) if n.is_integer() && n.num().abs() <= MAX_MULTIPLE && n != 1 => {
+                    let (_, gcd) = angles.iter().find(|(other, _)| *other == rest).unwrap();
+                    let base =
+                        binary(Operation::Mul, Function::Rational(gcd.clone()), rest).simplify();
+                    let hyperbolic = matches!(kind, FunctionType::Sinh | FunctionType::Cosh);
+                    let (sin, cos) = multiple_angle(n.num(), &base, hyperbolic);
+                    match kind {
+                        FunctionType::Sin | FunctionType::Sinh => sin,
+                        _ => cos,
+                    }
+                }
+                _ => special(kind.clone(), argument),
+            }
+        }
+        _ => func.clone(),
+    }
+}
+
+/// Lowers the even powers of sin and sinh (or cos and cosh) with the Pythagorean identities
+fn pythagorean(func: &Function, reduce_sin: bool) -> Function {
+    let sum = normalize(func, true);
+    let mut result = Vec::new();
+    let mut changed = false;
+
+    for term in &sum {
+        let mut product = Function::Rational(term.coeff.clone());
+        for (base, exp) in &term.factors {
+            let base = match base {
+                Function::Binary { .. } => pythagorean(base, reduce_sin),
+                _ => base.clone(),
+            };
+            let power = match (&base, exp) {
+                (Function::Special { kind, argument }, Function::Rational(exp))
+                    if exp.is_integer() && exp.num() >= 2 =>
+                {
+                    let square = |kind| {
+                        binary(
+                            Operation::Pow,
+                            special(kind, *argument.clone()),
+                            rational(2, 1),
+                        )
+                    };
+                    // sin^2 = 1 - cos^2, cos^2 = 1 - sin^2, sinh^2 = cosh^2 - 1, cosh^2 = 1 + sinh^2
+                    let replacement = match (kind, reduce_sin) {
+                        (FunctionType::Sin, true) => Some(binary(
+                            Operation::Sub,
+                            rational(1, 1),
+                            square(FunctionType::Cos),
+                        )),
+                        (FunctionType::Cos, false) => Some(binary(
+                            Operation::Sub,
+                            rational(1, 1),
+                            square(FunctionType::Sin),
+                        )),
+                        (FunctionType::Sinh, true) => Some(binary(
+                            Operation::Sub,
+                            square(FunctionType::Cosh),
+                            rational(1, 1),
+                        )),
+                        (FunctionType::Cosh, false) => Some(binary(
+                            Operation::Add,
+                            rational(1, 1),
+                            square(FunctionType::Sinh),
+                        )),
+                        _ => None,
+                    };
+                    match replacement {
+                        Some(replacement) => {
+                            changed = true;
+                            let (half, odd) = (exp.num() / 2, exp.num() % 2);
+                            binary(
+                                Operation::Mul,
+                                binary(Operation::Pow, base.clone(), rational(odd, 1)),
+                                binary(Operation::Pow, replacement, rational(half, 1)),
+                            )
+                        }
+                        None => binary(Operation::Pow, base, Function::Rational(exp.clone())),
+                    }
+                }
+                _ => binary(Operation::Pow, base, exp.clone()),
+            };
+            product = binary(Operation::Mul, product, power);
+        }
+        result.push(product);
+    }
+
+    let result = result
+        .into_iter()
+        .reduce(|acc, term| binary(Operation::Add, acc, term))
+        .unwrap_or(rational(0, 1));
+    match changed {
+        true => result.expand(),
+        false => result.simplify(),
+    }
+}
+
+/// Integer exponent of kind(argument) among the factors, zero if missing
+fn exponent(
+    factors: &[(Function, Function)],
+    kind: &FunctionType,
+    argument: &Function,
+) -> Option<i32> {
+    let found = factors.iter().find(|(base, _)| {
+        matches!(base, Function::Special { kind: other, argument: arg } if other == kind && **arg == *argument)
+    });
+    match found {
+        None => Some(0),
+        Some((_, Function::Rational(exp))) if exp.is_integer() => Some(exp.num()),
+        Some(_) => None,
+    }
+}
+
+/// Turns quotients and products of sin and cos back into tan, cot, sec, csc (and their
+/// hyperbolic counterparts), sin(u)cos(u) into sin(2u)/2
+fn contract(func: &Function) -> Function {
+    let families = [
+        [
+            FunctionType::Sin,
+            FunctionType::Cos,
+            FunctionType::Tan,
+            FunctionType::Cot,
+            FunctionType::Sec,
+            FunctionType::Csc,
+        ],
+        [
+            FunctionType::Sinh,
+            FunctionType::Cosh,
+            FunctionType::Tanh,
+            FunctionType::Coth,
+            FunctionType::Sech,
+            FunctionType::Csch,
+        ],
+    ];
+
+    let mut result = rational(0, 1);
+    for term in normalize(func, false) {
+        let mut factors = term.factors.clone();
+        let mut product = Function::Rational(term.coeff.clone());
+
+        for [sin, cos, tan, cot, sec, csc] in &families {
+            let arguments: Vec<Function> = factors
+                .iter()
+                .filter_map(|(base, _)| match base {
+                    Function::Special { kind, argument } if kind == sin || kind == cos => {
+                        Some(*argument.clone())
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            for argument in arguments {
+                let (a, b) = match (
+                    exponent(&factors, sin, &argument),
+                    exponent(&factors, cos, &argument),
+                ) {
+                    (Some(a), Some(b)) => (a, b),
+                    _ => continue,
+                };
+                let (kind, exp, new_argument) = match (a, b) {
+                    (a, b) if a > 0 && b == -a => (tan, a, argument.clone()),
+                    (a, b) if b > 0 && a == -b => (cot, b, argument.clone()),
+                    (0, b) if b < 0 => (sec, -b, argument.clone()),
+                    (a, 0) if a < 0 => (csc, -a, argument.clone()),
+                    (1, 1) => {
+                        product = binary(Operation::Mul, product, rational(1, 2));
+                        let double = binary(Operation::Mul, rational(2, 1), argument.clone());
+                        (sin, 1, double.simplify())
+                    }
+                    _ => continue,
+                };
+
+                factors.retain(|(base, _)| match base {
+                    Function::Special {
+                        kind,
+                        argument: arg,
+                    } => !((kind == sin || kind == cos) && **arg == argument),
+                    _ => true,
+                });
+                let power = binary(
+                    Operation::Pow,
+                    special(kind.clone(), new_argument),
+                    rational(exp, 1),
+                );
+                product = binary(Operation::Mul, product, power);
+            }
+        }
+
+        for (base, exp) in factors {
+            product = binary(Operation::Mul, product, binary(Operation::Pow, base, exp));
+        }
+        result = binary(Operation::Add, result, product);
+    }
+
+    to_function(&normalize(&result, false))
+}
+
+fn simplify_trig(func: &Function, form: TrigForm) -> Function {
+    let sin_cos = to_sin_cos(func);
+    let mut angles = Vec::new();
+    collect_angles(&sin_cos, &mut angles);
+    let expanded = expand_angles(&sin_cos, &angles);
+    let canonical = pythagorean(&expanded, true);
+
+    match form {
+        TrigForm::SinCos => canonical,
+        TrigForm::Shortest => {
+            let mut candidates = vec![func.simplify(), canonical.clone()];
+            candidates.push(pythagorean(&expanded, false));
+            candidates.push(pythagorean(&sin_cos, true));
+            candidates.push(pythagorean(&sin_cos, false));
+            let contracted: Vec<_> = candidates.iter().map(contract).collect();
+            candidates.extend(contracted);
+
+            candidates
+                .into_iter()
+                .min_by_key(|candidate| candidate.to_string().len())
+                .unwrap_or(canonical)
+        }
+    }
+}
+
+impl F1D {
+    /// Simplifies with trigonometric and hyperbolic identities
+    /// ```
+    /// use ruut_functions::{F1D, trig::TrigForm};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F1D::from_str("sin(x)^2+cos(x)^2").unwrap();
+    /// assert_eq!(func.simplify_trig(TrigForm::SinCos).to_string(), "1");
+    ///
+    /// let func = F1D::from_str("sin(2x)/cos(x)").unwrap();
+    /// assert_eq!(func.simplify_trig(TrigForm::Shortest).to_string(), "2sin(x)");
+    /// ```
+    pub fn simplify_trig(&self, form: TrigForm) -> Self {
+        F1D(simplify_trig(&self.0, form))
+    }
+}
+impl F2D {
+    /// Simplifies with trigonometric and hyperbolic identities
+    /// ```
+    /// use ruut_functions::{F2D, trig::TrigForm};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("cosh(xy)^2-sinh(xy)^2").unwrap();
+    /// assert_eq!(func.simplify_trig(TrigForm::Shortest).to_string(), "1");
+    /// ```
+    pub fn simplify_trig(&self, form: TrigForm) -> Self {
+        F2D(simplify_trig(&self.0, form))
+    }
+}
+impl F3D {
+    /// Simplifies with trigonometric and hyperbolic identities
+    /// ```
+    /// use ruut_functions::{F3D, trig::TrigForm};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("zsin(asin(x))+ycos(-x)").unwrap();
+    /// assert_eq!(func.simplify_trig(TrigForm::SinCos).to_string(), "xz+ycos(x)");
+    /// ```
+    pub fn simplify_trig(&self, form: TrigForm) -> Self {
+        F3D(simplify_trig(&self.0, form))
+    }
+}
+
+#[test]
+fn test_trig() {
+    use std::str::FromStr;
+
+    let cases = [
+        ("sin(x)^2+cos(x)^2", "1", "1"),
+        ("1+tan(x)^2", "1/(cos(x)^2)", "sec(x)^2"),
+        ("sin(2x)-2sin(x)cos(x)", "0", "0"),
+        ("cos(2x)+2sin(x)^2", "1", "1"),
+        ("sin(x)/cos(x)", "sin(x)/cos(x)", "tan(x)"),
+        ("sec(x)cos(x)", "1", "1"),
+        ("tanh(x)cosh(x)", "sinh(x)", "sinh(x)"),
+        ("cosh(2x)-2sinh(x)^2", "1", "1"),
+        ("sin(asin(x))+cos(acos(x))", "2x", "2x"),
+        ("2sin(x/2)cos(x/2)", "2sin(x/2)cos(x/2)", "sin(x)"),
+    ];
+    for (func, sin_cos, shortest) in cases {
+        let func = F1D::from_str(func).unwrap();
+        assert_eq!(
+            func.simplify_trig(TrigForm::SinCos).to_string(),
+            sin_cos,
+            "{func}"
+        );
+        assert_eq!(
+            func.simplify_trig(TrigForm::Shortest).to_string(),
+            shortest,
+            "{func}"
+        );
+    }
+
+    // The derivative of tan, written with sec and with sin/cos
+    let derivative = F1D::from_str("tan(x)").unwrap().derivative();
+    assert_eq!(
+        derivative.simplify_trig(TrigForm::SinCos),
+        F1D::from_str("1/cos(x)^2")
+            .unwrap()
+            .simplify_trig(TrigForm::SinCos)
+    );
+
+    for func in [
+        "sin(3x)+cos(x/2)^2",
+        "tanh(2x)sech(x)",
+        "cos(atan(x))+sin(acos(x))",
+    ] {
+        let func = F1D::from_str(func).unwrap();
+        for form in [TrigForm::SinCos, TrigForm::Shortest] {
+            let rewritten = func.simplify_trig(form);
+            for x in [0.3, 0.8, 1.7]
+                .into_iter()
+                .filter(|x| func.eval(*x).is_finite())
+            {
+                assert!(
+                    (rewritten.eval(x) - func.eval(x)).abs() < 1e-9,
+                    "{func} {rewritten}"
+                );
+            }
+        }
+    }
+}
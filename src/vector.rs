@@ -0,0 +1,271 @@
+use crate::{Function, Vec2, Vec3, F2D, F3D};
+
+impl Vec2<F2D> {
+    /// Evaluates the vector field at (x, y)
+    /// ```
+    /// use ruut_functions::{F2D, Vec2};
+    /// use std::str::FromStr;
+    ///
+    /// let field = Vec2 { x: F2D::from_str("xy").unwrap(), y: F2D::from_str("x+y").unwrap() };
+    ///
+    /// assert_eq!(field.eval(2., 3.), Vec2 { x: 6., y: 5. });
+    /// ```
+    pub fn eval(&self, x: f64, y: f64) -> Vec2<f64> {
+        Vec2 {
+            x: self.x.eval(x, y),
+            y: self.y.eval(x, y),
+        }
+    }
+
+    /// Computes the divergence ∂P/∂x + ∂Q/∂y
+    /// ```
+    /// use ruut_functions::{F2D, Vec2};
+    /// use std::str::FromStr;
+    ///
+    /// let field = Vec2 { x: F2D::from_str("x^2y").unwrap(), y: F2D::from_str("-xy^2").unwrap() };
+    ///
+    /// assert_eq!(field.divergence(), F2D::from_str("0").unwrap());
+    /// ```
+    pub fn divergence(&self) -> F2D {
+        F2D((self.x.0.derivative(1, 0, 0) + self.y.0.derivative(0, 1, 0)).simplify())
+    }
+
+    /// Computes the scalar curl ∂Q/∂x - ∂P/∂y
+    /// ```
+    /// use ruut_functions::{F2D, Vec2};
+    /// use std::str::FromStr;
+    ///
+    /// let field = Vec2 { x: F2D::from_str("-y").unwrap(), y: F2D::from_str("x").unwrap() };
+    ///
+    /// assert_eq!(field.curl(), F2D::from_str("2").unwrap());
+    /// ```
+    pub fn curl(&self) -> F2D {
+        F2D((self.y.0.derivative(1, 0, 0) - self.x.0.derivative(0, 1, 0)).simplify())
+    }
+}
+
+impl Vec3<F3D> {
+    /// Evaluates the vector field at (x, y, z)
+    /// ```
+    /// use ruut_functions::{F3D, Vec3};
+    /// use std::str::FromStr;
+    ///
+    /// let field = Vec3 {
+    ///     x: F3D::from_str("y").unwrap(),
+    ///     y: F3D::from_str("z").unwrap(),
+    ///     z: F3D::from_str("xyz").unwrap(),
+    /// };
+    ///
+    /// assert_eq!(field.eval(1., 2., 3.), Vec3 { x: 2., y: 3., z: 6. });
+    /// ```
+    pub fn eval(&self, x: f64, y: f64, z: f64) -> Vec3<f64> {
+        Vec3 {
+            x: self.x.eval(x, y, z),
+            y: self.y.eval(x, y, z),
+            z: self.z.eval(x, y, z),
+        }
+    }
+
+    /// Computes the divergence ∂P/∂x + ∂Q/∂y + ∂R/∂z
+    /// ```
+    /// use ruut_functions::{F3D, Vec3};
+    /// use std::str::FromStr;
+    ///
+    /// let field = Vec3 {
+    ///     x: F3D::from_str("x^2").unwrap(),
+    ///     y: F3D::from_str("yz").unwrap(),
+    ///     z: F3D::from_str("z").unwrap(),
+    /// };
+    ///
+    /// assert_eq!(field.divergence(), F3D::from_str("2x+z+1").unwrap().simplify());
+    /// ```
+    pub fn divergence(&self) -> F3D {
+        F3D((self.x.0.derivative(1, 0, 0)
+            + self.y.0.derivative(0, 1, 0)
+            + self.z.0.derivative(0, 0, 1))
+        .simplify())
+    }
+
+    /// Computes the curl (∂R/∂y - ∂Q/∂z, ∂P/∂z - ∂R/∂x, ∂Q/∂x - ∂P/∂y)
+    /// ```
+    /// use ruut_functions::{F3D, Vec3};
+    /// use std::str::FromStr;
+    ///
+    /// let field = Vec3 {
+    ///     x: F3D::from_str("-y").unwrap(),
+    ///     y: F3D::from_str("x").unwrap(),
+    ///     z: F3D::from_str("0").unwrap(),
+    /// };
+    ///
+    /// assert_eq!(field.curl().to_string(), "(0, 0, 2)");
+    /// ```
+    pub fn curl(&self) -> Vec3<F3D> {
+        let (p, q, r) = (&self.x.0, &self.y.0, &self.z.0);
+        Vec3 {
+            x: F3D((r.derivative(0, 1, 0) - q.derivative(0, 0, 1)).simplify()),
+            y: F3D((p.derivative(0, 0, 1) - r.derivative(1, 0, 0)).simplify()),
+            z: F3D((q.derivative(1, 0, 0) - p.derivative(0, 1, 0)).simplify()),
+        }
+    }
+}
+
+/// ∇f·v, simplified
+fn dot_gradient(func: &Function, direction: &[&Function]) -> Function {
+    direction
+        .iter()
+        .enumerate()
+        .map(|(i, component)| func.derivative_by(i) * (*component).clone())
+        .reduce(|acc, term| acc + term)
+        .map(|result| result.simplify())
+        .unwrap_or_else(|| func.clone())
+}
+
+/// Sum of the second derivatives of the first `vars` variables
+fn laplacian(func: &Function, vars: usize) -> Function {
+    (0..vars)
+        .map(|i| func.derivative_by(i).derivative_by(i))
+        .reduce(|acc, term| acc + term)
+        .map(|result| result.simplify())
+        .unwrap_or_else(|| func.clone())
+}
+
+impl F2D {
+    /// Computes the Laplacian ∂²f/∂x² + ∂²f/∂y²
+    /// ```
+    /// use ruut_functions::F2D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("x^2-y^2").unwrap();
+    ///
+    /// assert_eq!(func.laplacian(), F2D::from_str("0").unwrap());
+    /// ```
+    pub fn laplacian(&self) -> Self {
+        F2D(laplacian(&self.0, 2))
+    }
+
+    /// Computes the derivative along a vector, ∇f·v. It's the usual directional derivative when v
+    /// is a unit vector, v can also be a field
+    /// ```
+    /// use ruut_functions::{F2D, Vec2};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F2D::from_str("x^2y").unwrap();
+    /// let direction = Vec2 { x: F2D::from_str("1").unwrap(), y: F2D::from_str("2").unwrap() };
+    ///
+    /// assert_eq!(func.directional_derivative(&direction).eval(1., 1.), 4.);
+    /// ```
+    pub fn directional_derivative(&self, direction: &Vec2<F2D>) -> Self {
+        F2D(dot_gradient(&self.0, &[&direction.x.0, &direction.y.0]))
+    }
+}
+
+impl F3D {
+    /// Computes the Laplacian ∂²f/∂x² + ∂²f/∂y² + ∂²f/∂z²
+    /// ```
+    /// use ruut_functions::F3D;
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("x^2+y^2+z^2").unwrap();
+    ///
+    /// assert_eq!(func.laplacian(), F3D::from_str("6").unwrap());
+    /// ```
+    pub fn laplacian(&self) -> Self {
+        F3D(laplacian(&self.0, 3))
+    }
+
+    /// Computes the derivative along a vector, ∇f·v. It's the usual directional derivative when v
+    /// is a unit vector, v can also be a field
+    /// ```
+    /// use ruut_functions::{F3D, Vec3};
+    /// use std::str::FromStr;
+    ///
+    /// let func = F3D::from_str("xyz").unwrap();
+    /// let direction = Vec3 {
+    ///     x: F3D::from_str("0").unwrap(),
+    ///     y: F3D::from_str("0").unwrap(),
+    ///     z: F3D::from_str("1").unwrap(),
+    /// };
+    ///
+    /// assert_eq!(func.directional_derivative(&direction), F3D::from_str("xy").unwrap());
+    /// ```
+    pub fn directional_derivative(&self, direction: &Vec3<F3D>) -> Self {
+        F3D(dot_gradient(
+            &self.0,
+            &[&direction.x.0, &direction.y.0, &direction.z.0],
+        ))
+    }
+}
+
+#[test]
+fn test_vector() {
+    use std::str::FromStr;
+
+    let f3d = |s: &str| F3D::from_str(s).unwrap();
+
+    // The curl of a gradient and the divergence of a curl vanish
+    let func = f3d("x^2yz+sin(xz)");
+    assert_eq!(
+        func.derivative().curl(),
+        Vec3 {
+            x: f3d("0"),
+            y: f3d("0"),
+            z: f3d("0")
+        }
+    );
+    let field = Vec3 {
+        x: f3d("xy^2"),
+        y: f3d("e^(xz)"),
+        z: f3d("yln(x)"),
+    };
+    assert_eq!(field.curl().divergence(), f3d("0"));
+
+    // The divergence of a gradient is the laplacian
+    assert_eq!(func.derivative().divergence(), func.laplacian());
+
+    // Coulomb field, divergence free away from the origin
+    let field = Vec3 {
+        x: f3d("x/(x^2+y^2+z^2)^(3/2)"),
+        y: f3d("y/(x^2+y^2+z^2)^(3/2)"),
+        z: f3d("z/(x^2+y^2+z^2)^(3/2)"),
+    };
+    let divergence = field.divergence();
+    for (x, y, z) in [(1., 2., -1.), (0.3, -0.5, 0.7)] {
+        assert!(divergence.eval(x, y, z).abs() < 1e-12);
+    }
+    let value = field.eval(1., 0., 0.);
+    assert_eq!(
+        value,
+        Vec3 {
+            x: 1.,
+            y: 0.,
+            z: 0.
+        }
+    );
+
+    // Along a unit vector, compared with a finite difference
+    let func = f3d("x^2+yz^3");
+    let (u, v, w) = (0.6, 0., 0.8);
+    let direction = Vec3 {
+        x: f3d("3/5"),
+        y: f3d("0"),
+        z: f3d("4/5"),
+    };
+    let derivative = func.directional_derivative(&direction);
+    let (x, y, z, h) = (1., 2., 0.5, 1e-6);
+    let numeric = (func.eval(x + h * u, y + h * v, z + h * w) - func.eval(x, y, z)) / h;
+    assert!((derivative.eval(x, y, z) - numeric).abs() < 1e-4);
+
+    let func = F2D::from_str("e^xsin(y)").unwrap();
+    assert_eq!(func.laplacian(), F2D::from_str("0").unwrap());
+    let rotation = Vec2 {
+        x: F2D::from_str("-y").unwrap(),
+        y: F2D::from_str("x").unwrap(),
+    };
+    assert_eq!(rotation.divergence(), F2D::from_str("0").unwrap());
+    // Rotations keep the distance from the origin
+    let func = F2D::from_str("x^2+y^2").unwrap();
+    assert_eq!(
+        func.directional_derivative(&rotation),
+        F2D::from_str("0").unwrap()
+    );
+}